@@ -0,0 +1,56 @@
+// Compares the single-pass scanner of day 1 with the former `find` and `rfind` per token,
+// on generated inputs of growing size.
+//      cargo bench --bench day1 [-- <samples>]
+use aoc_2023::bench::Stats;
+use aoc_2023::day1::{self, Mode};
+use aoc_2023::generate;
+use std::env;
+use std::time::{Duration, Instant};
+
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+fn measure(samples: usize, solve: impl Fn() -> u64) -> (u64, Stats) {
+    let mut value = 0;
+    let durations = (0..samples)
+        .map(|_| {
+            let start = Instant::now();
+            value = solve();
+            start.elapsed()
+        })
+        .collect::<Vec<Duration>>();
+
+    (value, Stats::new(&durations).unwrap())
+}
+
+fn main() {
+    // `cargo bench` passes `--bench` along.
+    let samples = env::args()
+        .skip(1)
+        .find(|arg| !arg.starts_with("--"))
+        .map_or(10, |arg| {
+            arg.parse::<usize>().expect("samples must be a number")
+        });
+
+    println!(
+        "{:>10}  {:>12}  {:>12}  {:>7}",
+        "lines", "find", "scanner", "speedup"
+    );
+
+    for size in SIZES {
+        let input = generate::generate(1, 0, size).unwrap().input;
+        let (expected, find) = measure(samples, || {
+            day1::get_calibration_value_find(&input, Mode::DigitsAndWords.vocabulary()).unwrap()
+        });
+        let (value, scanner) = measure(samples, || {
+            day1::get_calibration_value(&input, Mode::DigitsAndWords.vocabulary()).unwrap()
+        });
+
+        assert_eq!(value, expected, "the scanner disagrees on {size} lines");
+        println!(
+            "{size:>10}  {:>12?}  {:>12?}  {:>6.1}x",
+            find.median,
+            scanner.median,
+            find.median.as_secs_f64() / scanner.median.as_secs_f64()
+        );
+    }
+}
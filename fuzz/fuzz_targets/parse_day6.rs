@@ -0,0 +1,8 @@
+#![no_main]
+
+use aoc_2023::registry;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = (registry::find(6).unwrap().solve)(input, &[1, 2]);
+});
@@ -0,0 +1,134 @@
+use crate::toml_file;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+// Default file of the accepted answers, in the working directory.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+// Accepted answers keyed by day and part, e.g.
+//      [day3]
+//      part1 = "4361"
+//      part2 = 467835
+// Answers may be written as strings or integers.
+#[derive(Default)]
+pub struct KnownAnswers {
+    days: BTreeMap<String, BTreeMap<String, toml::Value>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing,
+}
+
+impl KnownAnswers {
+    pub fn load(path: &Path) -> Result<KnownAnswers, String> {
+        toml_file::load(path).map(|days| KnownAnswers { days })
+    }
+
+    pub fn parse(contents: &str) -> Result<KnownAnswers, String> {
+        toml::from_str(contents)
+            .map(|days| KnownAnswers { days })
+            .map_err(|err| err.message().to_owned())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        toml_file::save(path, &self.days)
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<String> {
+        match self
+            .days
+            .get(&format!("day{day}"))?
+            .get(&format!("part{part}"))?
+        {
+            toml::Value::String(answer) => Some(answer.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    pub fn set(&mut self, day: u32, part: u32, answer: &str) {
+        self.days.entry(format!("day{day}")).or_default().insert(
+            format!("part{part}"),
+            toml::Value::String(answer.to_owned()),
+        );
+    }
+
+    pub fn check(&self, day: u32, part: u32, actual: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected,
+                actual: actual.to_owned(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "fail (expected {expected}, got {actual})")
+            }
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = "[day3]
+part1 = \"4361\"
+part2 = 467835
+
+[day8]
+part1 = \"6\"
+";
+
+    #[test]
+    fn test_get_strings_and_integers() {
+        let answers = KnownAnswers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.get(3, 1), Some("4361".to_owned()));
+        assert_eq!(answers.get(3, 2), Some("467835".to_owned()));
+        assert_eq!(answers.get(8, 2), None);
+        assert_eq!(answers.get(4, 1), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = KnownAnswers::parse(ANSWERS).unwrap();
+
+        assert_eq!(answers.check(3, 1, "4361"), Verdict::Pass);
+        assert_eq!(
+            answers.check(3, 2, "467836"),
+            Verdict::Fail {
+                expected: "467835".to_owned(),
+                actual: "467836".to_owned()
+            }
+        );
+        assert_eq!(answers.check(8, 2, "6"), Verdict::Missing);
+    }
+
+    #[test]
+    fn test_set_replaces_answer() {
+        let mut answers = KnownAnswers::parse(ANSWERS).unwrap();
+        answers.set(3, 1, "4362");
+        answers.set(6, 2, "71503");
+
+        assert_eq!(answers.get(3, 1), Some("4362".to_owned()));
+        assert_eq!(answers.get(6, 2), Some("71503".to_owned()));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(KnownAnswers::parse("[day3\npart1 = 1").is_err());
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    // `None` without any sample. The median of an even count is the lower middle one.
+    pub fn new(samples: &[Duration]) -> Option<Stats> {
+        let mut samples = samples.to_vec();
+        samples.sort();
+
+        Some(Stats {
+            min: *samples.first()?,
+            median: samples[(samples.len() - 1) / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_odd_samples() {
+        let samples = [5, 1, 3].map(Duration::from_millis);
+
+        assert_eq!(
+            Stats::new(&samples),
+            Some(Stats {
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(3),
+                max: Duration::from_millis(5),
+            })
+        );
+    }
+
+    #[test]
+    fn test_stats_even_samples() {
+        let samples = [4, 1, 3, 2].map(Duration::from_millis);
+
+        assert_eq!(
+            Stats::new(&samples).unwrap().median,
+            Duration::from_millis(2)
+        );
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        assert_eq!(Stats::new(&[]), None);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::toml_file;
+use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+
+// Default file of the cached answers, in the working directory.
+pub const CACHE_FILE: &str = ".aoc_cache.toml";
+
+// Hash of the contents of an input. The standard hasher may change between Rust releases,
+// which only costs a new run of every day.
+pub fn input_hash(input: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Answers keyed by day, part, solver version and input hash, e.g.
+//      [day3.part1]
+//      v1-5d2cbb8f4a1c0e37 = "4361"
+#[derive(Default)]
+pub struct AnswerCache {
+    days: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+fn key(version: u32, hash: u64) -> String {
+    format!("v{version}-{hash:016x}")
+}
+
+impl AnswerCache {
+    pub fn load(path: &Path) -> Result<AnswerCache, String> {
+        toml_file::load(path).map(|days| AnswerCache { days })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        toml_file::save(path, &self.days)
+    }
+
+    pub fn get(&self, day: u32, part: u32, version: u32, hash: u64) -> Option<&str> {
+        self.days
+            .get(&format!("day{day}"))?
+            .get(&format!("part{part}"))?
+            .get(&key(version, hash))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, version: u32, hash: u64, answer: &str) {
+        self.days
+            .entry(format!("day{day}"))
+            .or_default()
+            .entry(format!("part{part}"))
+            .or_default()
+            .insert(key(version, hash), answer.to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_needs_same_version_and_input() {
+        let hash = input_hash("Card 1: 41 | 41");
+        let mut cache = AnswerCache::default();
+        cache.insert(4, 1, 1, hash, "1");
+
+        assert_eq!(cache.get(4, 1, 1, hash), Some("1"));
+        assert_eq!(cache.get(4, 1, 2, hash), None);
+        assert_eq!(cache.get(4, 1, 1, input_hash("Card 1: 41 | 42")), None);
+        assert_eq!(cache.get(4, 2, 1, hash), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut cache = AnswerCache::default();
+        cache.insert(8, 2, 3, 42, "6");
+        let contents = toml::to_string(&cache.days).unwrap();
+        let cache = AnswerCache {
+            days: toml::from_str(&contents).unwrap(),
+        };
+
+        assert_eq!(cache.get(8, 2, 3, 42), Some("6"));
+    }
+}
@@ -1,51 +1,582 @@
-const DIGITS: [&str; 20] = [
-    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "0", "1", "2",
-    "3", "4", "5", "6", "7", "8", "9",
+use crate::error::Error;
+use crate::solution::Solution;
+use crate::stream::fold_lines;
+use log::debug;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::BufRead;
+use std::sync::OnceLock;
+
+const ENGLISH: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const FRENCH: [&str; 10] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+const GERMAN: [&str; 10] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const SPANISH: [&str; 10] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
 ];
+// Zeros of the Unicode blocks of decimal digits, each followed by 1 to 9.
+const UNICODE_ZEROS: [char; 8] = [
+    '\u{0660}',  // Arabic-Indic
+    '\u{06F0}',  // Extended Arabic-Indic
+    '\u{0966}',  // Devanagari
+    '\u{09E6}',  // Bengali
+    '\u{0E50}',  // Thai
+    '\u{FF10}',  // Full-width
+    '\u{1D7CE}', // Mathematical bold
+    '\u{1D7F6}', // Mathematical monospace
+];
+
+// Which tokens are read as digits: part 1 only reads numeric digits, part 2 spelled ones too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    DigitsOnly,
+    DigitsAndWords,
+}
+
+impl Mode {
+    pub fn for_part(part: u32) -> Mode {
+        match part {
+            1 => Mode::DigitsOnly,
+            _ => Mode::DigitsAndWords,
+        }
+    }
+
+    pub fn vocabulary(self) -> &'static DigitVocabulary {
+        static DIGITS_ONLY: OnceLock<DigitVocabulary> = OnceLock::new();
+        static DIGITS_AND_WORDS: OnceLock<DigitVocabulary> = OnceLock::new();
+
+        match self {
+            Mode::DigitsOnly => DIGITS_ONLY.get_or_init(DigitVocabulary::digits),
+            Mode::DigitsAndWords => DIGITS_AND_WORDS
+                .get_or_init(|| DigitVocabulary::english().with(&DigitVocabulary::digits())),
+        }
+    }
+}
+
+// Aho-Corasick automaton, matching every token in a single pass over a line.
+struct Automaton {
+    // Next state for every state and byte, the root being 0.
+    transitions: Vec<[u32; 256]>,
+    // Tokens ending in each state, by increasing index.
+    matches: Vec<Vec<usize>>,
+}
+
+impl Automaton {
+    // `tokens` are sequences of characters, each given by its alternative encodings. The
+    // alternatives of a single byte lead to the same state, longer ones to their own states.
+    fn new(tokens: &[Vec<Vec<Vec<u8>>>]) -> Automaton {
+        let mut automaton = Automaton {
+            transitions: vec![[0; 256]],
+            matches: vec![Vec::new()],
+        };
+
+        // Trie of the tokens, where 0 means no child since nothing leads back to the root.
+        for (idx, token) in tokens.iter().enumerate() {
+            let mut states = vec![0];
+
+            for alternatives in token {
+                let mut next = Vec::new();
+
+                for state in states {
+                    let mut merged = None;
+
+                    for bytes in alternatives {
+                        match bytes[..] {
+                            [byte] => {
+                                let child = merged.unwrap_or_else(|| automaton.child(state, byte));
+                                automaton.transitions[state][byte as usize] = child as u32;
+                                merged = Some(child);
+                                next.push(child);
+                            }
+                            _ => next.push(
+                                bytes
+                                    .iter()
+                                    .fold(state, |state, byte| automaton.child(state, *byte)),
+                            ),
+                        }
+                    }
+                }
+
+                next.sort();
+                next.dedup();
+                states = next;
+            }
+
+            for state in states {
+                automaton.matches[state].push(idx);
+            }
+        }
+
+        let Automaton {
+            mut transitions,
+            mut matches,
+        } = automaton;
+
+        // Breadth first, so that the failure state of a state is complete before it.
+        let mut failures = vec![0; transitions.len()];
+        let mut queued = vec![false; transitions.len()];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(state) = queue.pop_front() {
+            let fallbacks = transitions[failures[state]];
+
+            for (next, fallback) in transitions[state].iter_mut().zip(fallbacks) {
+                if *next == 0 {
+                    *next = fallback;
+                } else if !queued[*next as usize] {
+                    let child = *next as usize;
+                    queued[child] = true;
+                    failures[child] = if state == 0 { 0 } else { fallback as usize };
+                    let inherited = matches[failures[child]].clone();
+                    matches[child].extend(inherited);
+                    matches[child].sort();
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        Automaton {
+            transitions,
+            matches,
+        }
+    }
+
+    // The state reached from `state` on `byte`, created if needed.
+    fn child(&mut self, state: usize, byte: u8) -> usize {
+        if self.transitions[state][byte as usize] == 0 {
+            self.transitions[state][byte as usize] = self.transitions.len() as u32;
+            self.transitions.push([0; 256]);
+            self.matches.push(Vec::new());
+        }
+
+        self.transitions[state][byte as usize] as usize
+    }
+}
+
+// `c` and, when ignoring case, its other cases of the same UTF-8 length, so that a token
+// keeps its length whatever its case.
+fn case_variants(c: char, ignore_case: bool) -> Vec<char> {
+    let mut variants = vec![c];
+
+    if ignore_case {
+        let cases = [
+            c.to_lowercase().collect::<String>(),
+            c.to_uppercase().collect::<String>(),
+        ];
+
+        for case in cases {
+            let mut chars = case.chars();
+
+            if let (Some(other), None) = (chars.next(), chars.next()) {
+                if other.len_utf8() == c.len_utf8() && !variants.contains(&other) {
+                    variants.push(other);
+                }
+            }
+        }
+    }
+
+    variants
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TokenMatch {
+    // Index of the token in its vocabulary.
+    token: usize,
+    // Byte offset of the first byte of the token in the line.
+    start: usize,
+}
+
+// Finds the first and the last token of a line, running one automaton forwards and
+// another one over the reversed tokens backwards.
+struct Scanner {
+    lengths: Vec<usize>,
+    forward: Automaton,
+    backward: Automaton,
+    longest: usize,
+}
+
+impl Scanner {
+    fn new(tokens: &[&str], ignore_case: bool) -> Scanner {
+        let encode = |token: &str, reversed: bool| {
+            let mut chars = token
+                .chars()
+                .map(|c| {
+                    case_variants(c, ignore_case)
+                        .into_iter()
+                        .map(|variant| {
+                            let mut bytes = variant.to_string().into_bytes();
+                            if reversed {
+                                bytes.reverse();
+                            }
+                            bytes
+                        })
+                        .collect()
+                })
+                .collect::<Vec<Vec<Vec<u8>>>>();
+            if reversed {
+                chars.reverse();
+            }
+            chars
+        };
+        let forward = tokens
+            .iter()
+            .map(|token| encode(token, false))
+            .collect::<Vec<_>>();
+        let backward = tokens
+            .iter()
+            .map(|token| encode(token, true))
+            .collect::<Vec<_>>();
+
+        Scanner {
+            lengths: tokens.iter().map(|token| token.len()).collect(),
+            forward: Automaton::new(&forward),
+            backward: Automaton::new(&backward),
+            longest: tokens.iter().map(|token| token.len()).max().unwrap_or(0),
+        }
+    }
+
+    // The leftmost token, the first token on ties.
+    fn first(&self, line: &[u8]) -> Option<TokenMatch> {
+        let mut state = 0;
+        let mut best: Option<TokenMatch> = None;
+
+        for (idx, byte) in line.iter().enumerate() {
+            // Tokens are reported by their end, so a longer one ending later may still start
+            // before the best one until the longest token no longer fits.
+            if best.is_some_and(|best| idx >= best.start + self.longest) {
+                break;
+            }
+
+            state = self.forward.transitions[state][*byte as usize] as usize;
+
+            for token in &self.forward.matches[state] {
+                let found = TokenMatch {
+                    token: *token,
+                    start: idx + 1 - self.lengths[*token],
+                };
+
+                if best.is_none_or(|best| (found.start, found.token) < (best.start, best.token)) {
+                    best = Some(found);
+                }
+            }
+        }
+
+        best
+    }
+
+    // The rightmost token, the first token on ties.
+    fn last(&self, line: &[u8]) -> Option<TokenMatch> {
+        let mut state = 0;
+
+        // Going backwards, tokens are reported by their start, so the first one is the last.
+        for (idx, byte) in line.iter().enumerate().rev() {
+            state = self.backward.transitions[state][*byte as usize] as usize;
+
+            if let Some(token) = self.backward.matches[state].first() {
+                return Some(TokenMatch {
+                    token: *token,
+                    start: idx,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+// Tokens read as digits, each with its value. Built from `(token, value)` pairs, e.g. to
+// read calibration lines mixing languages:
+//      DigitVocabulary::digits().with(&DigitVocabulary::french()).with(&DigitVocabulary::german())
+// Tokens match whatever their case, unless switched off with `case_sensitive`.
+pub struct DigitVocabulary {
+    // When a token is given twice, its first value wins.
+    pairs: Vec<(String, u32)>,
+    ignore_case: bool,
+    scanner: Scanner,
+}
+
+impl DigitVocabulary {
+    // Tokens can't be empty and values are single digits.
+    pub fn new<S: Into<String>>(
+        pairs: impl IntoIterator<Item = (S, u32)>,
+    ) -> Result<DigitVocabulary, String> {
+        let pairs = pairs
+            .into_iter()
+            .map(|(token, value)| (token.into(), value))
+            .collect::<Vec<(String, u32)>>();
+
+        if let Some((token, value)) = pairs.iter().find(|(_, value)| *value > 9) {
+            return Err(format!("value {value} of `{token}` is not a single digit"));
+        }
+        if pairs.iter().any(|(token, _)| token.is_empty()) {
+            return Err("empty digit token".to_owned());
+        }
+
+        Ok(DigitVocabulary::compile(pairs, true))
+    }
+
+    fn compile(pairs: Vec<(String, u32)>, ignore_case: bool) -> DigitVocabulary {
+        let tokens = pairs
+            .iter()
+            .map(|(token, _)| token.as_str())
+            .collect::<Vec<&str>>();
+        let scanner = Scanner::new(&tokens, ignore_case);
+
+        DigitVocabulary {
+            pairs,
+            ignore_case,
+            scanner,
+        }
+    }
+
+    pub fn case_sensitive(self, case_sensitive: bool) -> DigitVocabulary {
+        DigitVocabulary::compile(self.pairs, !case_sensitive)
+    }
+
+    // Spelled digits, from zero to nine.
+    fn words(words: &[&str; 10]) -> DigitVocabulary {
+        DigitVocabulary::new(words.iter().copied().zip(0..)).unwrap()
+    }
+
+    // ASCII digits.
+    pub fn digits() -> DigitVocabulary {
+        DigitVocabulary::new((0..10).map(|value| (value.to_string(), value))).unwrap()
+    }
+
+    pub fn english() -> DigitVocabulary {
+        DigitVocabulary::words(&ENGLISH)
+    }
+
+    pub fn french() -> DigitVocabulary {
+        DigitVocabulary::words(&FRENCH)
+    }
+
+    pub fn german() -> DigitVocabulary {
+        DigitVocabulary::words(&GERMAN)
+    }
+
+    pub fn spanish() -> DigitVocabulary {
+        DigitVocabulary::words(&SPANISH)
+    }
+
+    // `０` to `９`.
+    pub fn full_width() -> DigitVocabulary {
+        DigitVocabulary::unicode_block('\u{FF10}')
+    }
+
+    // Decimal digits of the common non-ASCII scripts, full-width ones included.
+    pub fn unicode_digits() -> DigitVocabulary {
+        DigitVocabulary::new(
+            UNICODE_ZEROS
+                .iter()
+                .flat_map(|zero| DigitVocabulary::block_pairs(*zero)),
+        )
+        .unwrap()
+    }
+
+    fn block_pairs(zero: char) -> impl Iterator<Item = (String, u32)> {
+        (0..10).map(move |value| {
+            let digit = char::from_u32(zero as u32 + value).unwrap();
+            (digit.to_string(), value)
+        })
+    }
+
+    fn unicode_block(zero: char) -> DigitVocabulary {
+        DigitVocabulary::new(DigitVocabulary::block_pairs(zero)).unwrap()
+    }
 
-fn convert_digit(digit: &str) -> u32 {
-    match digit {
-        "zero" => 0,
-        "one" => 1,
-        "two" => 2,
-        "three" => 3,
-        "four" => 4,
-        "five" => 5,
-        "six" => 6,
-        "seven" => 7,
-        "eight" => 8,
-        "nine" => 9,
-        _ => digit.parse::<u32>().unwrap_or(0),
+    // Both vocabularies, the tokens and the case sensitivity of `self` winning over `other`.
+    pub fn with(self, other: &DigitVocabulary) -> DigitVocabulary {
+        let pairs = self.pairs.into_iter().chain(other.pairs.iter().cloned());
+
+        DigitVocabulary::compile(pairs.collect(), self.ignore_case)
+    }
+
+    fn found<'a>(&self, line: &'a str, token_match: &TokenMatch) -> FoundToken<'a> {
+        let end = token_match.start + self.scanner.lengths[token_match.token];
+
+        FoundToken {
+            text: &line[token_match.start..end],
+            offset: token_match.start,
+            value: self.pairs[token_match.token].1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoundToken<'a> {
+    // As written in the line, whatever its case.
+    pub text: &'a str,
+    // Byte offset in the line.
+    pub offset: usize,
+    pub value: u32,
+}
+
+// How the value of a line is read, e.g.
+//      "xtwone3four" first="two"@1 last="four"@7 -> 24
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineExplanation<'a> {
+    pub line: &'a str,
+    // First and last tokens, the same one when the line has a single digit.
+    pub digits: Option<(FoundToken<'a>, FoundToken<'a>)>,
+    pub value: u32,
+}
+
+impl fmt::Display for LineExplanation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.digits {
+            Some((first, last)) => write!(
+                f,
+                "{:?} first={:?}@{} last={:?}@{} -> {}",
+                self.line, first.text, first.offset, last.text, last.offset, self.value
+            ),
+            None => write!(f, "{:?} no digit -> {}", self.line, self.value),
+        }
+    }
+}
+
+pub fn explain_line<'a>(line: &'a str, vocabulary: &DigitVocabulary) -> LineExplanation<'a> {
+    let scanner = &vocabulary.scanner;
+    let digits = scanner
+        .first(line.as_bytes())
+        .zip(scanner.last(line.as_bytes()))
+        .map(|(first, last)| {
+            (
+                vocabulary.found(line, &first),
+                vocabulary.found(line, &last),
+            )
+        });
+
+    LineExplanation {
+        line,
+        digits,
+        value: digits.map_or(0, |(first, last)| first.value * 10 + last.value),
     }
 }
 
-fn get_line_value(line: &str) -> u32 {
+// Explanations of every line, numbered from 1.
+pub fn explain_calibration<'a>(
+    block: &'a str,
+    vocabulary: &'a DigitVocabulary,
+) -> impl Iterator<Item = (usize, LineExplanation<'a>)> + 'a {
+    block
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, explain_line(line, vocabulary)))
+}
+
+fn get_line_value(line: &str, vocabulary: &DigitVocabulary) -> u32 {
+    let explanation = explain_line(line, vocabulary);
+    debug!("{explanation}");
+
+    explanation.value
+}
+
+// Lowercase of every char that keeps its UTF-8 length, the other chars as they are,
+// which matches the case variants read by the scanner.
+fn fold_case(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(other), None) if other.len_utf8() == c.len_utf8() => other,
+                _ => c,
+            }
+        })
+        .collect()
+}
+
+// One `find` and one `rfind` per token, so 40 scans of each line with spelled digits.
+// Kept as the reference of the scanner for the tests and the benchmark.
+fn get_line_value_find(line: &str, vocabulary: &DigitVocabulary) -> u32 {
+    let fold = |text: &str| match vocabulary.ignore_case {
+        true => fold_case(text),
+        false => text.to_owned(),
+    };
+    let line = fold(line);
     let mut first_digit = 0;
     let mut first_pos = line.len();
     let mut last_digit = 0;
     let mut last_pos: Option<usize> = None;
 
-    for digit in DIGITS {
-        if let Some(pos) = line.find(digit) {
+    for (digit, value) in &vocabulary.pairs {
+        let digit = fold(digit);
+
+        if let Some(pos) = line.find(digit.as_str()) {
             if pos < first_pos {
-                first_digit = convert_digit(digit);
+                first_digit = *value;
                 first_pos = pos;
             }
         }
 
-        if let Some(pos) = line.rfind(digit) {
+        if let Some(pos) = line.rfind(digit.as_str()) {
             if last_pos.is_none() || pos > last_pos.unwrap() {
-                last_digit = convert_digit(digit);
+                last_digit = *value;
                 last_pos = Some(pos);
             }
         }
     }
 
-    format!("{first_digit}{last_digit}").parse::<u32>().unwrap()
+    first_digit * 10 + last_digit
+}
+
+fn sum_overflow() -> Error {
+    Error::solve(1, "sum of the calibration values overflows")
 }
 
-pub fn get_calibration_value(block: &str) -> u32 {
-    block.lines().map(|line| get_line_value(line)).sum()
+pub fn get_calibration_value_find(block: &str, vocabulary: &DigitVocabulary) -> Result<u64, Error> {
+    block
+        .lines()
+        .try_fold(0_u64, |sum, line| {
+            sum.checked_add(get_line_value_find(line, vocabulary) as u64)
+        })
+        .ok_or_else(sum_overflow)
+}
+
+pub fn get_calibration_value(block: &str, vocabulary: &DigitVocabulary) -> Result<u64, Error> {
+    block
+        .lines()
+        .try_fold(0_u64, |sum, line| {
+            sum.checked_add(get_line_value(line, vocabulary) as u64)
+        })
+        .ok_or_else(sum_overflow)
+}
+
+// Same as the solver, reading one line at a time.
+pub fn stream_calibration_value(
+    reader: impl BufRead,
+    vocabulary: &DigitVocabulary,
+) -> Result<u64, Error> {
+    fold_lines(reader, 0_u64, |sum, line, _| {
+        sum.checked_add(get_line_value(line, vocabulary) as u64)
+            .ok_or_else(sum_overflow)
+    })
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = &'a str;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    // Each part scans the raw lines with its own vocabulary, so there is nothing to parse.
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        Ok(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        get_calibration_value(input, Mode::for_part(1).vocabulary())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        get_calibration_value(input, Mode::for_part(2).vocabulary())
+    }
 }
 
 #[cfg(test)]
@@ -55,56 +586,248 @@ mod tests {
     #[test]
     fn test_line_calibration_value_simple() {
         let line = "1abc2";
-        let value = get_line_value(line);
+        let value = get_line_value(line, Mode::DigitsAndWords.vocabulary());
         assert_eq!(value, 12);
     }
 
     #[test]
     fn test_line_calibration_value_simple_digit_letters() {
         let line = "1abc2zero";
-        let value = get_line_value(line);
+        let value = get_line_value(line, Mode::DigitsAndWords.vocabulary());
         assert_eq!(value, 10);
     }
 
     #[test]
     fn test_line_calibration_value_simple_digit_letters_digit_between() {
         let line = "one2one";
-        let value = get_line_value(line);
+        let value = get_line_value(line, Mode::DigitsAndWords.vocabulary());
         assert_eq!(value, 11);
     }
 
     #[test]
     fn test_line_calibration_value_digit_wrapped_by_letters() {
         let line = "pqr3stu8vwx";
-        let value = get_line_value(line);
+        let value = get_line_value(line, Mode::DigitsAndWords.vocabulary());
         assert_eq!(value, 38);
     }
 
     #[test]
     fn test_line_calibration_value_only_one_digit_wrapped_by_letters() {
         let line = "treb7uchet";
-        let value = get_line_value(line);
+        let value = get_line_value(line, Mode::DigitsAndWords.vocabulary());
         assert_eq!(value, 77);
     }
 
     #[test]
     fn test_line_calibration_value_with_letters_digit() {
         let line = "fourtreb7uchet";
-        let value = get_line_value(line);
+        let value = get_line_value(line, Mode::DigitsAndWords.vocabulary());
         assert_eq!(value, 47);
     }
 
+    #[test]
+    fn test_line_calibration_value_overlapping_words() {
+        assert_eq!(
+            get_line_value("eightwo", Mode::DigitsAndWords.vocabulary()),
+            82
+        );
+        assert_eq!(
+            get_line_value("xoneight", Mode::DigitsAndWords.vocabulary()),
+            18
+        );
+        assert_eq!(
+            get_line_value("twone", Mode::DigitsAndWords.vocabulary()),
+            21
+        );
+        assert_eq!(
+            get_line_value("sevenine", Mode::DigitsAndWords.vocabulary()),
+            79
+        );
+    }
+
+    #[test]
+    fn test_line_calibration_value_no_digit() {
+        assert_eq!(
+            get_line_value("abcdef", Mode::DigitsAndWords.vocabulary()),
+            0
+        );
+        assert_eq!(
+            get_line_value_find("abcdef", Mode::DigitsAndWords.vocabulary()),
+            0
+        );
+    }
+
+    #[test]
+    fn test_line_calibration_value_digits_only() {
+        assert_eq!(
+            get_line_value("two1nine", Mode::DigitsOnly.vocabulary()),
+            11
+        );
+        assert_eq!(
+            get_line_value("4nineeightseven2", Mode::DigitsOnly.vocabulary()),
+            42
+        );
+        assert_eq!(get_line_value("eightwo", Mode::DigitsOnly.vocabulary()), 0);
+    }
+
+    #[test]
+    fn test_parts_read_different_tokens() {
+        let input = Day1::parse("two1nine\nabcone2threexyz").unwrap();
+
+        assert_eq!(Day1::part1(&input), Ok(33));
+        assert_eq!(Day1::part2(&input), Ok(42));
+    }
+
+    #[test]
+    fn test_bundled_vocabularies() {
+        let digits = DigitVocabulary::digits();
+
+        assert_eq!(
+            get_line_value("x7deuxtroisneuf", &DigitVocabulary::french().with(&digits)),
+            79
+        );
+        assert_eq!(get_line_value("fünfzehn", &DigitVocabulary::german()), 55);
+        assert_eq!(
+            get_line_value("cuatro2seis", &DigitVocabulary::spanish().with(&digits)),
+            46
+        );
+        assert_eq!(
+            get_line_value("ab３cd８", &DigitVocabulary::full_width()),
+            38
+        );
+        assert_eq!(
+            get_line_value("٤x५", &DigitVocabulary::unicode_digits()),
+            45
+        );
+    }
+
+    #[test]
+    fn test_mixed_vocabularies() {
+        let vocabulary = DigitVocabulary::english()
+            .with(&DigitVocabulary::french())
+            .with(&DigitVocabulary::german())
+            .with(&DigitVocabulary::digits());
+
+        assert_eq!(get_line_value("huit3twodrei", &vocabulary), 83);
+        assert_eq!(get_line_value("sixsechs", &vocabulary), 66);
+    }
+
+    #[test]
+    fn test_vocabulary_from_pairs() {
+        let vocabulary = DigitVocabulary::new([("i", 1), ("v", 5), ("i", 9)]).unwrap();
+
+        assert_eq!(get_line_value("xviix", &vocabulary), 51);
+        assert_eq!(
+            DigitVocabulary::new([("ten", 10)]).err(),
+            Some("value 10 of `ten` is not a single digit".to_owned())
+        );
+        assert_eq!(
+            DigitVocabulary::new([("", 0)]).err(),
+            Some("empty digit token".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_line_calibration_value_ignores_case() {
+        let vocabulary = Mode::DigitsAndWords.vocabulary();
+
+        assert_eq!(get_line_value("ONE2Three", vocabulary), 13);
+        assert_eq!(get_line_value("xOnEiGhT", vocabulary), 18);
+        assert_eq!(get_line_value("FÜNFzwei", &DigitVocabulary::german()), 52);
+        assert_eq!(get_line_value("ZÉROun", &DigitVocabulary::french()), 1);
+    }
+
+    #[test]
+    fn test_find_follows_case_setting() {
+        let vocabulary = DigitVocabulary::english().with(&DigitVocabulary::digits());
+
+        assert_eq!(
+            get_calibration_value_find("ONE2\nZÉRO3", &vocabulary),
+            Ok(12 + 33)
+        );
+        assert_eq!(
+            get_line_value_find("FÜNFzwei", &DigitVocabulary::german()),
+            52
+        );
+        assert_eq!(
+            get_line_value_find("ONE2", &vocabulary.case_sensitive(true)),
+            22
+        );
+    }
+
+    #[test]
+    fn test_line_calibration_value_case_sensitive() {
+        let vocabulary = DigitVocabulary::english()
+            .with(&DigitVocabulary::digits())
+            .case_sensitive(true);
+
+        assert_eq!(get_line_value("ONE2Three", &vocabulary), 22);
+        assert_eq!(get_line_value("one2three", &vocabulary), 13);
+        assert_eq!(
+            get_line_value("ONE2", &vocabulary.case_sensitive(false)),
+            12
+        );
+    }
+
+    #[test]
+    fn test_explain_line() {
+        let explanation = explain_line("xtwone3four", Mode::DigitsAndWords.vocabulary());
+
+        assert_eq!(
+            explanation.to_string(),
+            r#""xtwone3four" first="two"@1 last="four"@7 -> 24"#
+        );
+        assert_eq!(
+            explain_line("xtwone3four", Mode::DigitsOnly.vocabulary()).to_string(),
+            r#""xtwone3four" first="3"@6 last="3"@6 -> 33"#
+        );
+        assert_eq!(
+            explain_line("abc", Mode::DigitsOnly.vocabulary()).to_string(),
+            r#""abc" no digit -> 0"#
+        );
+    }
+
+    #[test]
+    fn test_explain_keeps_case_and_offsets() {
+        let explanation = explain_line("zFÜNFx", &DigitVocabulary::german());
+
+        assert_eq!(
+            explanation
+                .digits
+                .map(|(first, last)| (first.text, last.offset)),
+            Some(("FÜNF", 1))
+        );
+    }
+
+    #[test]
+    fn test_explain_calibration_numbers_lines() {
+        let input = "two1nine\n\nzoneight234";
+        let explanations = explain_calibration(input, Mode::DigitsAndWords.vocabulary())
+            .map(|(number, explanation)| (number, explanation.value))
+            .collect::<Vec<(usize, u32)>>();
+
+        assert_eq!(explanations, vec![(1, 29), (2, 0), (3, 14)]);
+    }
+
     #[test]
     fn test_input_calibration_value_happy() {
         let input: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet";
-        let value = get_calibration_value(input);
+        let value = get_calibration_value(input, Mode::DigitsOnly.vocabulary()).unwrap();
         assert_eq!(value, 142);
     }
 
     #[test]
     fn test_input_calibration_value_happy_with_letters_digit() {
         let input: &str = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
-        let value = get_calibration_value(input);
+        let value = get_calibration_value(input, Mode::DigitsAndWords.vocabulary()).unwrap();
+        assert_eq!(value, 281);
+    }
+
+    #[test]
+    fn test_stream_calibration_value() {
+        let input: &str = "Two1nine\r\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+        let value =
+            stream_calibration_value(input.as_bytes(), Mode::DigitsAndWords.vocabulary()).unwrap();
         assert_eq!(value, 281);
     }
 
@@ -112,7 +835,7 @@ mod tests {
     fn test_input_calibration_value_empty() {
         // Assumption: Empty input gives sum of 0.
         let input: &str = "";
-        let value = get_calibration_value(input);
+        let value = get_calibration_value(input, Mode::DigitsAndWords.vocabulary()).unwrap();
         assert_eq!(value, 0);
     }
 
@@ -120,7 +843,74 @@ mod tests {
     fn test_input_calibration_value_only_newlines() {
         // Assumption: Empty but newlines only input gives sum of 0.
         let input: &str = "\n\n\n";
-        let value = get_calibration_value(input);
+        let value = get_calibration_value(input, Mode::DigitsAndWords.vocabulary()).unwrap();
         assert_eq!(value, 0);
     }
 }
+
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_scanner_matches_find(
+            line in "(zero|one|two|three|four|five|six|seven|eight|nine|[0-9]|[a-z]){0,16}"
+        ) {
+            for vocabulary in [Mode::DigitsOnly.vocabulary(), Mode::DigitsAndWords.vocabulary()] {
+                prop_assert_eq!(
+                    get_line_value(&line, vocabulary),
+                    get_line_value_find(&line, vocabulary)
+                );
+            }
+        }
+
+        #[test]
+        fn test_scanner_matches_find_across_languages(
+            line in "(un|deux|eins|zwei|uno|dos|six|seis|sechs|[0-9]|[a-zü]|[０-９]){0,16}"
+        ) {
+            let vocabulary = DigitVocabulary::english()
+                .with(&DigitVocabulary::french())
+                .with(&DigitVocabulary::german())
+                .with(&DigitVocabulary::spanish())
+                .with(&DigitVocabulary::full_width());
+
+            prop_assert_eq!(
+                get_line_value(&line, &vocabulary),
+                get_line_value_find(&line, &vocabulary)
+            );
+        }
+
+        #[test]
+        fn test_scanner_matches_find_on_any_text(line in "\\PC{0,40}") {
+            for vocabulary in [Mode::DigitsOnly.vocabulary(), Mode::DigitsAndWords.vocabulary()] {
+                prop_assert_eq!(
+                    get_line_value(&line, vocabulary),
+                    get_line_value_find(&line.to_ascii_lowercase(), vocabulary)
+                );
+            }
+        }
+
+        #[test]
+        fn test_scanner_ignores_case(
+            line in "(one|two|three|eight|nine|fünf|zéro|[0-9]|[a-z]){0,12}",
+            uppercase in proptest::collection::vec(any::<bool>(), 0..80)
+        ) {
+            let vocabulary = DigitVocabulary::english()
+                .with(&DigitVocabulary::german())
+                .with(&DigitVocabulary::french())
+                .with(&DigitVocabulary::digits());
+            let mixed = line
+                .chars()
+                .zip(uppercase.into_iter().chain(std::iter::repeat(false)))
+                .map(|(c, upper)| if upper { c.to_uppercase().next().unwrap() } else { c })
+                .collect::<String>();
+
+            prop_assert_eq!(
+                get_line_value(&mixed, &vocabulary),
+                get_line_value_find(&line, &vocabulary)
+            );
+        }
+    }
+}
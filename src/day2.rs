@@ -1,49 +1,59 @@
-use regex::Regex;
+use crate::error::{column_of, Error};
+use crate::solution::Solution;
+use crate::stream::fold_lines;
+use std::io::BufRead;
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseGameError;
+// Cubes loaded in the bag for part 1.
+const BAG_BLUE: u32 = 14;
+const BAG_RED: u32 = 12;
+const BAG_GREEN: u32 = 13;
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseGameSetError;
-
-struct Game {
+pub struct Game {
     pub sets: Vec<GameSet>,
     pub id: u32,
 }
 
-struct GameSet {
+pub struct GameSet {
     pub red: u32,
     pub blue: u32,
     pub green: u32,
 }
 
 impl FromStr for GameSet {
-    type Err = ParseGameSetError;
+    type Err = Error;
 
+    // e.g. ` 3 blue, 4 red`
     fn from_str(set: &str) -> Result<Self, Self::Err> {
-        let pattern = r"(?P<redGroup>\d+ red)|(?P<greenGroup>\d+ green)|(?P<blueGroup>\d+ blue)";
-        let re = Regex::new(pattern).unwrap();
         let mut result = GameSet {
             red: 0,
             blue: 0,
             green: 0,
         };
 
-        let group_names: Vec<&str> = re.capture_names().skip(1).map(|x| x.unwrap()).collect();
-
-        for caps in re.captures_iter(set) {
-            for name in &group_names {
-                if let Some(m) = caps.name(name) {
-                    let digit_ends = m.as_str().find(' ').unwrap();
-                    let value = m.as_str()[..digit_ends].parse::<u32>().unwrap();
-
-                    match name {
-                        &"greenGroup" => result.green = value,
-                        &"blueGroup" => result.blue = value,
-                        &"redGroup" => result.red = value,
-                        _ => panic!("Unknown color group {name}"),
-                    }
+        for cubes in set.split(',').map(str::trim) {
+            let (count, color) = cubes.split_once(' ').ok_or_else(|| {
+                Error::parse(2, column_of(set, cubes), "expected `<count> <color>`")
+            })?;
+
+            let value = count.parse::<u32>().map_err(|_| {
+                Error::parse(
+                    2,
+                    column_of(set, count),
+                    format!("invalid cube count `{count}`"),
+                )
+            })?;
+
+            match color.to_ascii_lowercase().as_str() {
+                "green" => result.green = value,
+                "blue" => result.blue = value,
+                "red" => result.red = value,
+                _ => {
+                    return Err(Error::parse(
+                        2,
+                        column_of(set, color),
+                        format!("unknown color `{color}`"),
+                    ))
                 }
             }
         }
@@ -62,61 +72,127 @@ impl Game {
 }
 
 impl FromStr for Game {
-    type Err = ParseGameError;
+    type Err = Error;
 
+    // e.g. `Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green`
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let bound = input.find(':').unwrap();
-
-        Ok(Game {
-            id: input[5..bound].parse::<u32>().unwrap(),
-            sets: get_game_sets_str(input)
-                .iter()
-                .map(|set| GameSet::from_str(set).unwrap())
-                .collect::<Vec<GameSet>>(),
-        })
+        let (header, _) = input
+            .split_once(':')
+            .ok_or_else(|| Error::parse(2, input.len() + 1, "expected ':' after the game id"))?;
+
+        let id = match header.split_once(' ') {
+            Some((game, id)) if game.eq_ignore_ascii_case("game") => id,
+            _ => return Err(Error::parse(2, 1, "expected `Game <id>`")),
+        };
+
+        let id = id.trim().parse::<u32>().map_err(|_| {
+            Error::parse(2, column_of(input, id), format!("invalid game id `{id}`"))
+        })?;
+
+        let sets = get_game_sets_str(input)
+            .iter()
+            .map(|set| GameSet::from_str(set).map_err(|err| err.shifted(column_of(input, set) - 1)))
+            .collect::<Result<Vec<GameSet>, Error>>()?;
+
+        Ok(Game { id, sets })
     }
 }
 
-pub fn get_valid_games_id_sums(input: &str, blue: u32, red: u32, green: u32) -> u32 {
-    get_games(input)
+pub fn get_valid_games_id_sums(
+    games: &[Game],
+    blue: u32,
+    red: u32,
+    green: u32,
+) -> Result<u64, Error> {
+    games
         .iter()
         .filter(|game| game.satisfies_constraints(blue, red, green))
-        .map(|game| game.id)
-        .sum()
+        .try_fold(0_u64, |sum, game| sum.checked_add(game.id as u64))
+        .ok_or_else(ids_overflow)
 }
 
-pub fn get_games_power(input: &str) -> u32 {
-    get_games(input)
-        .iter()
-        .map(|game| {
-            let blue = game.sets.iter().max_by_key(|set| set.blue).unwrap().blue;
-            let green = game.sets.iter().max_by_key(|set| set.green).unwrap().green;
-            let red = game.sets.iter().max_by_key(|set| set.red).unwrap().red;
+fn ids_overflow() -> Error {
+    Error::solve(2, "sum of the game ids overflows")
+}
 
-            blue * green * red
-        })
-        .sum()
+fn power_overflow() -> Error {
+    Error::solve(2, "sum of the game powers overflows")
 }
 
-fn get_games(input: &str) -> Vec<Game> {
-    let games_str: Vec<&str> = input.lines().collect();
+// `None` when the power does not fit in 64 bits.
+fn get_game_power(game: &Game) -> Option<u64> {
+    let blue = game.sets.iter().map(|set| set.blue).max().unwrap_or(0);
+    let green = game.sets.iter().map(|set| set.green).max().unwrap_or(0);
+    let red = game.sets.iter().map(|set| set.red).max().unwrap_or(0);
+
+    (blue as u64 * green as u64).checked_mul(red as u64)
+}
 
-    games_str
+pub fn get_games_power(games: &[Game]) -> Result<u64, Error> {
+    games
         .iter()
-        .map(|game| Game::from_str(game).unwrap())
-        .collect::<Vec<Game>>()
+        .try_fold(0_u64, |sum, game| {
+            get_game_power(game).and_then(|power| sum.checked_add(power))
+        })
+        .ok_or_else(power_overflow)
+}
+
+// Answers of both parts, reading one game at a time.
+pub fn stream_games(reader: impl BufRead) -> Result<(u64, u64), Error> {
+    fold_lines(reader, (0_u64, 0_u64), |(ids, power), line, line_number| {
+        let game = Game::from_str(line).map_err(|err| err.on_line(line_number))?;
+        let id = match game.satisfies_constraints(BAG_BLUE, BAG_RED, BAG_GREEN) {
+            true => game.id,
+            false => 0,
+        };
+
+        Ok((
+            ids.checked_add(id as u64).ok_or_else(ids_overflow)?,
+            get_game_power(&game)
+                .and_then(|game_power| power.checked_add(game_power))
+                .ok_or_else(power_overflow)?,
+        ))
+    })
+}
+
+fn get_games(input: &str) -> Result<Vec<Game>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, game)| Game::from_str(game).map_err(|err| err.on_line(idx + 1)))
+        .collect()
 }
 
 fn get_game_sets_str(game: &str) -> Vec<&str> {
-    let sets_start = game.find(':').unwrap() + 1; // Exclude the char itself.
-    let sets = game[sets_start..].split(';').collect();
+    let (_, sets) = game.split_once(':').unwrap_or_default(); // Exclude the char itself.
+
+    sets.split(';').collect()
+}
 
-    sets
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<Game>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        get_games(input)
+    }
+
+    fn part1(games: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        get_valid_games_id_sums(games, BAG_BLUE, BAG_RED, BAG_GREEN)
+    }
+
+    fn part2(games: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        get_games_power(games)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::example;
 
     #[test]
     fn test_get_game_set() {
@@ -140,8 +216,8 @@ mod tests {
 
     #[test]
     fn test_parse_games_log() {
-        const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        let values = get_games(&INPUT);
+        let input = example(2, 1);
+        let values = get_games(&input).unwrap();
 
         assert_eq!(values.len(), 5);
         assert_eq!(values[0].id, 1);
@@ -158,28 +234,127 @@ mod tests {
 
     #[test]
     fn test_valid_games_id_sum() {
-        const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        let sum = get_valid_games_id_sums(&INPUT, 14, 12, 13);
+        let input = example(2, 1);
+        let sum = get_valid_games_id_sums(&get_games(&input).unwrap(), 14, 12, 13);
 
-        assert_eq!(sum, 8);
+        assert_eq!(sum, Ok(8));
     }
 
     #[test]
     fn test_games_power_sum() {
-        const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\nGame 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\nGame 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red\nGame 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        let sum = get_games_power(&INPUT);
+        let input = example(2, 1);
+        let sum = get_games_power(&get_games(&input).unwrap());
 
-        assert_eq!(sum, 2286);
+        assert_eq!(sum, Ok(2286));
+    }
+
+    #[test]
+    fn test_games_power_overflow() {
+        let input = "Game 1: 4000000000 blue, 4000000000 red, 4000000000 green";
+        let overflow = Error::solve(2, "sum of the game powers overflows");
+
+        assert_eq!(
+            get_games_power(&get_games(input).unwrap()),
+            Err(overflow.clone())
+        );
+        assert_eq!(stream_games(input.as_bytes()), Err(overflow));
     }
 
     #[test]
     fn test_game_satisfies_constraints() {
         const INPUT: &str = "Game 1: 3 blue, 4 red";
-        let values = get_games(&INPUT);
+        let values = get_games(INPUT).unwrap();
 
         assert!(!values[0].satisfies_constraints(4, 1, 0)); // Fails because red.
         assert!(values[0].satisfies_constraints(4, 5, 1)); // Passes.
         assert!(!values[0].satisfies_constraints(2, 5, 0)); // Fails because blue.
         assert!(values[0].satisfies_constraints(3, 4, 0)); // Pass.
     }
+
+    #[test]
+    fn test_game_missing_colon() {
+        let err = get_games("Game 1: 3 blue\nGame 2 3 blue").err().unwrap();
+
+        assert_eq!(
+            err,
+            Error::parse(2, 14, "expected ':' after the game id").on_line(2)
+        );
+    }
+
+    #[test]
+    fn test_game_unknown_color() {
+        let err = Game::from_str("Game 7: 3 blue; 4 purple").err().unwrap();
+
+        assert_eq!(err, Error::parse(2, 19, "unknown color `purple`"));
+    }
+
+    #[test]
+    fn test_stream_games() {
+        let input = example(2, 1);
+
+        assert_eq!(stream_games(input.as_bytes()), Ok((8, 2286)));
+    }
+
+    #[test]
+    fn test_stream_games_locates_errors() {
+        let err = stream_games("Game 1: 3 blue\nGame 2 3 blue".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            Error::parse(2, 14, "expected ':' after the game id").on_line(2)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn assert_located(result: Result<Game, Error>, line: &str) {
+        if let Err(Error::Parse {
+            line: 1, column, ..
+        }) = result
+        {
+            assert!(
+                (1..=line.len() + 1).contains(&column),
+                "column {column} of {line:?}"
+            );
+        } else if let Err(err) = result {
+            panic!("unexpected {err:?} for {line:?}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_game_round_trip(
+            id in 0..100_000_u32,
+            sets in prop::collection::vec((0..100_u32, 0..100_u32, 0..100_u32), 1..6),
+        ) {
+            let line = format!(
+                "Game {id}: {}",
+                sets.iter()
+                    .map(|(red, green, blue)| format!("{blue} blue, {red} red, {green} green"))
+                    .collect::<Vec<String>>()
+                    .join("; ")
+            );
+            let game = Game::from_str(&line).unwrap();
+
+            prop_assert_eq!(game.id, id);
+            prop_assert_eq!(
+                game.sets.iter().map(|set| (set.red, set.green, set.blue)).collect::<Vec<_>>(),
+                sets
+            );
+        }
+
+        #[test]
+        fn test_game_never_panics(line in "\\PC*") {
+            assert_located(Game::from_str(&line), &line);
+        }
+
+        #[test]
+        fn test_game_like_never_panics(line in "(Game|game|)[ 0-9]{0,4}:?[ 0-9a-z,;]{0,30}") {
+            assert_located(Game::from_str(&line), &line);
+        }
+    }
 }
@@ -1,7 +1,10 @@
+use crate::error::{column_of, Error};
+use crate::solution::Solution;
 use grid::Grid;
+use log::{debug, trace};
 use regex::Regex;
 
-type EngineMap = Grid<char>;
+pub type EngineMap = Grid<char>;
 
 fn is_symbol(c: &char) -> bool {
     !(c.is_ascii_digit() || c == &'.')
@@ -76,24 +79,55 @@ fn is_slice_adjacent_to_symbol(
     false
 }
 
-fn get_engine_map(input: &str) -> EngineMap {
+fn get_engine_map(input: &str) -> Result<EngineMap, Error> {
     let mut engine = EngineMap::new(0, 0);
 
-    input.lines().for_each(|line| {
-        engine.insert_row(engine.rows(), line.trim().chars().collect());
-    });
+    for (idx, line) in input.lines().enumerate() {
+        let trimmed = line.trim();
+        let row = trimmed.chars().collect::<Vec<char>>();
+
+        // The grid needs every row to be as wide as the first one.
+        if idx > 0 && row.len() != engine.cols() {
+            // Errors count bytes, the grid counts chars.
+            let offset = trimmed
+                .char_indices()
+                .nth(engine.cols())
+                .map_or(trimmed.len(), |(offset, _)| offset);
+
+            return Err(Error::parse(
+                3,
+                column_of(line, trimmed) + offset,
+                format!("expected {} columns, found {}", engine.cols(), row.len()),
+            )
+            .on_line(idx + 1));
+        }
+
+        engine.insert_row(engine.rows(), row);
+    }
+
+    Ok(engine)
+}
+
+fn parse_part_number(number: &str) -> Result<u32, Error> {
+    number
+        .parse::<u32>()
+        .map_err(|_| Error::solve(3, format!("part number {number} is too large")))
+}
 
-    engine
+// Column of a match, counted in chars like the engine map.
+fn column_of_match(line: &str, start: usize) -> usize {
+    line[..start].chars().count()
 }
 
+// Only ASCII digits form numbers, other digits are symbols like any other char.
 fn get_numbers_in_line(line: &str) -> Vec<(usize, String)> {
     let mut result = Vec::<(usize, String)>::new();
-    let pattern = r"(\d+)";
+    let pattern = r"([0-9]+)";
     let re = Regex::new(pattern).unwrap();
     re.captures_iter(line).for_each(|capture| {
         capture.iter().skip(1).for_each(|m| {
             let m = m.unwrap();
-            result.push((m.start(), line[m.start()..m.end()].to_string()));
+            result.push((column_of_match(line, m.start()), m.as_str().to_string()));
         })
     });
 
@@ -107,7 +141,7 @@ fn get_gears_in_line(line: &str) -> Vec<(usize, String)> {
     re.captures_iter(line).for_each(|capture| {
         capture.iter().skip(1).for_each(|m| {
             let m = m.unwrap();
-            result.push((m.start(), line[m.start()..m.end()].to_string()));
+            result.push((column_of_match(line, m.start()), m.as_str().to_string()));
         })
     });
 
@@ -121,7 +155,7 @@ fn get_part_numbers(engine: &EngineMap) -> Vec<((u32, u32), String)> {
         let numbers = get_numbers_in_line(&cols.into_iter().collect::<String>());
 
         numbers.iter().for_each(|(col_idx, number)| {
-            result.push(((row_idx as u32, col_idx.clone() as u32), number.into()));
+            result.push(((row_idx as u32, *col_idx as u32), number.into()));
         });
     });
 
@@ -135,17 +169,24 @@ fn get_gears(engine: &EngineMap) -> Vec<(u32, u32)> {
         let numbers = get_gears_in_line(&cols.into_iter().collect::<String>());
 
         numbers.iter().for_each(|(col_idx, _)| {
-            result.push((row_idx as u32, col_idx.clone() as u32));
+            result.push((row_idx as u32, *col_idx as u32));
         });
     });
 
     result
 }
 
-pub fn get_gears_value(input: &str) -> u32 {
-    let engine = get_engine_map(&input);
-    let gears = get_gears(&engine);
-    let numbers = get_part_numbers(&engine);
+fn sum_overflow() -> Error {
+    Error::solve(3, "sum of the part numbers overflows")
+}
+
+fn ratios_overflow() -> Error {
+    Error::solve(3, "sum of the gear ratios overflows")
+}
+
+pub fn get_gears_value(engine: &EngineMap) -> Result<u64, Error> {
+    let gears = get_gears(engine);
+    let numbers = get_part_numbers(engine);
 
     gears
         .iter()
@@ -155,7 +196,7 @@ pub fn get_gears_value(input: &str) -> u32 {
                 .filter(|(coord, number)| {
                     let (gear_x, gear_y) = (gear.0 as i32, gear.1 as i32);
                     let px = coord.0 as i32;
-                    let mut i = 0 as i32;
+                    let mut i = 0_i32;
 
                     if (px - gear_x).abs() <= 1 {
                         while i < number.len() as i32 {
@@ -174,57 +215,78 @@ pub fn get_gears_value(input: &str) -> u32 {
                 .map(|(_, number)| number.clone())
                 .collect::<Vec<String>>();
 
+            debug!(
+                "gear at row {}, column {}: adjacent to {adj_numbers:?}",
+                gear.0, gear.1
+            );
+
             if adj_numbers.len() == 2 {
                 return adj_numbers;
             }
 
-            return Vec::<String>::new();
+            Vec::<String>::new()
         })
-        .map(|numbers| {
+        .try_fold(0_u64, |total, numbers| {
             // An empty vector product is 1 :shrug:
             if numbers.is_empty() {
-                return 0;
+                return Ok(total);
             }
 
-            numbers
-                .iter()
-                .map(|num| num.parse::<u32>().unwrap())
-                .product::<u32>()
+            let ratio = numbers.iter().try_fold(1_u64, |ratio, num| {
+                ratio
+                    .checked_mul(parse_part_number(num)? as u64)
+                    .ok_or_else(ratios_overflow)
+            })?;
+
+            total.checked_add(ratio).ok_or_else(ratios_overflow)
         })
-        .sum()
 }
 
-pub fn get_final_value(input: &str) -> u32 {
-    let engine = get_engine_map(&input);
-    let numbers = get_part_numbers(&engine);
+pub fn get_final_value(engine: &EngineMap) -> Result<u64, Error> {
+    let numbers = get_part_numbers(engine);
 
     numbers
         .iter()
         .filter(|((x, y), number)| {
-            is_slice_adjacent_to_symbol(&engine, (*x, *y), (*x, y + number.len() as u32))
+            let adjacent =
+                is_slice_adjacent_to_symbol(engine, (*x, *y), (*x, y + number.len() as u32));
+            trace!("number {number} at row {x}, column {y}: adjacent to a symbol: {adjacent}");
+            adjacent
+        })
+        .try_fold(0_u64, |sum, (_, number)| {
+            sum.checked_add(parse_part_number(number)? as u64)
+                .ok_or_else(sum_overflow)
         })
-        .map(|(_, number)| number.parse::<u32>().unwrap())
-        .sum()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = EngineMap;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        get_engine_map(input)
+    }
+
+    fn part1(engine: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        get_final_value(engine)
+    }
+
+    fn part2(engine: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        get_gears_value(engine)
+    }
 }
 
 #[cfg(test)]
 mod tests_adjacence {
     use super::*;
-
-    const INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
+    use crate::fixtures::example;
 
     #[test]
     fn test_adjacent_bottom_right() {
-        let engine = get_engine_map(INPUT);
+        let engine = get_engine_map(&example(3, 1)).unwrap();
         let (row, col) = (0, 0);
         assert!(is_slice_adjacent_to_symbol(
             &engine,
@@ -235,7 +297,7 @@ mod tests_adjacence {
 
     #[test]
     fn test_adjacent_right() {
-        let engine = get_engine_map(INPUT);
+        let engine = get_engine_map(&example(3, 1)).unwrap();
         let (row, col) = (4, 0);
         assert!(is_slice_adjacent_to_symbol(
             &engine,
@@ -246,7 +308,7 @@ mod tests_adjacence {
 
     #[test]
     fn test_adjacent_top_right() {
-        let engine = get_engine_map(INPUT);
+        let engine = get_engine_map(&example(3, 1)).unwrap();
         let (row, col) = (6, 2);
         assert!(is_slice_adjacent_to_symbol(
             &engine,
@@ -257,7 +319,7 @@ mod tests_adjacence {
 
     #[test]
     fn test_adjacent_bottom_left() {
-        let engine = get_engine_map(INPUT);
+        let engine = get_engine_map(&example(3, 1)).unwrap();
         let (row, col) = (7, 6);
         assert!(is_slice_adjacent_to_symbol(
             &engine,
@@ -270,23 +332,13 @@ mod tests_adjacence {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
+    use crate::fixtures::example;
 
     fn engine_as_string(engine: &EngineMap) -> String {
         let mut result = String::new();
 
         engine.iter_rows().for_each(|row| {
-            row.for_each(|col| result.push(col.clone()));
+            row.for_each(|col| result.push(*col));
             result.push('\n');
         });
 
@@ -295,7 +347,7 @@ mod tests {
 
     #[test]
     fn test_get_engine_map() {
-        let engine = get_engine_map(INPUT);
+        let engine = get_engine_map(&example(3, 1)).unwrap();
 
         assert_eq!(engine.rows(), 10);
         assert_eq!(engine.cols(), 10);
@@ -303,32 +355,32 @@ mod tests {
         assert_eq!(engine.get(9, 7).unwrap(), &'8');
         assert_eq!(engine.get(4, 3).unwrap(), &'*');
 
-        assert_eq!(INPUT, engine_as_string(&engine));
+        assert_eq!(example(3, 1), engine_as_string(&engine));
     }
 
     #[test]
     fn test_is_symbol() {
-        assert_eq!(is_symbol(&'.'), false);
-        assert_eq!(is_symbol(&'6'), false);
-        assert_eq!(is_symbol(&'0'), false);
-        assert_eq!(is_symbol(&'*'), true);
-        assert_eq!(is_symbol(&'&'), true);
-        assert_eq!(is_symbol(&'%'), true);
-        assert_eq!(is_symbol(&'$'), true);
-        assert_eq!(is_symbol(&'/'), true);
-        assert_eq!(is_symbol(&'@'), true);
-        assert_eq!(is_symbol(&' '), true);
+        assert!(!is_symbol(&'.'));
+        assert!(!is_symbol(&'6'));
+        assert!(!is_symbol(&'0'));
+        assert!(is_symbol(&'*'));
+        assert!(is_symbol(&'&'));
+        assert!(is_symbol(&'%'));
+        assert!(is_symbol(&'$'));
+        assert!(is_symbol(&'/'));
+        assert!(is_symbol(&'@'));
+        assert!(is_symbol(&' '));
     }
 
     #[test]
     fn test_get_game_set() {
-        let value = get_final_value(INPUT);
+        let value = get_final_value(&get_engine_map(&example(3, 1)).unwrap()).unwrap();
         assert_eq!(value, 4361);
     }
 
     #[test]
     fn test_get_part_numbers() {
-        let engine = get_engine_map(INPUT);
+        let engine = get_engine_map(&example(3, 1)).unwrap();
         let numbers = get_part_numbers(&engine);
 
         assert_eq!(numbers.len(), 10);
@@ -344,9 +396,106 @@ mod tests {
         assert_eq!(result[1], (7, "488".to_owned()));
     }
 
+    #[test]
+    fn test_get_line_numbers_only_ascii_digits() {
+        let result = get_numbers_in_line("٣.12");
+        assert_eq!(result, vec![(2, "12".to_owned())]);
+    }
+
+    #[test]
+    fn test_non_ascii_symbols() {
+        let engine = get_engine_map("é.....\n.12..3\n٣....*").unwrap();
+
+        assert_eq!(Day3::part1(&engine), Ok(15));
+        assert_eq!(get_gears(&engine), vec![(2, 5)]);
+    }
+
     #[test]
     fn test_gears_output() {
-        let result = get_gears_value(INPUT);
+        let result = get_gears_value(&get_engine_map(&example(3, 1)).unwrap()).unwrap();
         assert_eq!(result, 467835);
     }
+
+    #[test]
+    fn test_large_gear_ratio() {
+        let engine = get_engine_map("123456*123456").unwrap();
+
+        assert_eq!(Day3::part1(&engine), Ok(246912));
+        assert_eq!(Day3::part2(&engine), Ok(15241383936));
+    }
+
+    #[test]
+    fn test_gear_ratios_overflow() {
+        let gear = "4294967295*4294967295";
+        let input = format!("{gear}\n{}\n{gear}", ".".repeat(gear.len()));
+        let engine = get_engine_map(&input).unwrap();
+
+        assert_eq!(
+            Day3::part2(&engine),
+            Err(Error::solve(3, "sum of the gear ratios overflows"))
+        );
+    }
+
+    #[test]
+    fn test_get_engine_map_ragged_row() {
+        let err = get_engine_map("467..\n...*.\n..35").err().unwrap();
+
+        assert_eq!(
+            err,
+            Error::parse(3, 5, "expected 5 columns, found 4").on_line(3)
+        );
+    }
+
+    #[test]
+    fn test_get_engine_map_ragged_row_counts_bytes() {
+        let err = get_engine_map("..\n é٣.").err().unwrap();
+
+        assert_eq!(
+            err,
+            Error::parse(3, 6, "expected 2 columns, found 3").on_line(2)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    // Rows of the same width.
+    fn rows() -> impl Strategy<Value = Vec<String>> {
+        (1..20_usize).prop_flat_map(|width| {
+            prop::collection::vec(
+                proptest::string::string_regex(&format!("[0-9.*#$]{{{width}}}")).unwrap(),
+                1..10,
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_engine_map_round_trip(rows in rows()) {
+            let engine = get_engine_map(&rows.join("\n")).unwrap();
+
+            prop_assert_eq!(
+                engine.iter_rows().map(|row| row.collect::<String>()).collect::<Vec<String>>(),
+                rows
+            );
+        }
+
+        #[test]
+        fn test_engine_map_never_panics(input in "\\PC*(\n\\PC*){0,4}") {
+            let _ = get_engine_map(&input);
+        }
+
+        #[test]
+        fn test_engine_map_ragged_rows(input in "[0-9.*]{0,5}(\n[0-9.*]{0,5}){0,4}") {
+            let widths = input.lines().map(str::len).collect::<Vec<usize>>();
+
+            prop_assert_eq!(
+                get_engine_map(&input).is_ok(),
+                widths.iter().all(|width| *width == widths[0])
+            );
+        }
+    }
 }
@@ -1,11 +1,28 @@
-pub fn solve_scratchcards(input: &str) -> u32 {
-    get_cards(input).iter().map(calculate_points_card).sum()
+use crate::error::{column_of, Error};
+use crate::solution::Solution;
+use crate::stream::fold_lines;
+use log::{debug, trace};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+pub fn solve_scratchcards(cards: &[Card]) -> Result<u64, Error> {
+    cards.iter().try_fold(0_u64, |total, card| {
+        calculate_points_card(card)
+            .and_then(|points| total.checked_add(points))
+            .ok_or_else(points_overflow)
+    })
 }
 
-// Returns: number of total scratch cards after bonuses
-pub fn solve_bonus_total_scratchcards(input: &str) -> u32 {
-    let cards = get_cards(input);
+fn points_overflow() -> Error {
+    Error::solve(4, "total points overflow")
+}
 
+fn copies_overflow() -> Error {
+    Error::solve(4, "number of scratchcards overflows")
+}
+
+// Returns: number of total scratch cards after bonuses
+pub fn solve_bonus_total_scratchcards(cards: &[Card]) -> Result<u64, Error> {
     // Vector of winnings
     //      [card 1, card 2, ...]
     // e.g. [     4,      2, ...]
@@ -13,28 +30,98 @@ pub fn solve_bonus_total_scratchcards(input: &str) -> u32 {
         .iter()
         .map(calculate_times_won_card)
         .collect::<Vec<u32>>();
-    let mut cards_count = Vec::<u32>::new();
+    let mut cards_count = Vec::<u64>::new();
     cards_count.resize(cards.len(), 1); // 1 card each
 
     // Process per card and changes the amount per card.
-    wins_per_card
+    for (idx, numbers_won) in wins_per_card.iter().enumerate() {
+        // Update the next cards count
+        let bound = *numbers_won as usize;
+
+        if idx + bound >= cards.len() {
+            return Err(Error::solve(
+                4,
+                format!("card {} wins copies past the last card", cards[idx].id),
+            ));
+        }
+
+        debug!(
+            "card {}: {numbers_won} wins, {} copies",
+            cards[idx].id, cards_count[idx]
+        );
+
+        for update_idx in 0..bound {
+            cards_count[idx + 1 + update_idx] = cards_count[idx + 1 + update_idx]
+                .checked_add(cards_count[idx])
+                .ok_or_else(copies_overflow)?;
+            trace!(
+                "card {} now has {} copies",
+                cards[idx + 1 + update_idx].id,
+                cards_count[idx + 1 + update_idx]
+            );
+        }
+    }
+
+    cards_count
         .iter()
-        .enumerate()
-        .for_each(|(idx, numbers_won)| {
-            // Update the next cards count
-            let bound = *numbers_won as usize;
-            for update_idx in 0..bound {
-                cards_count[idx + 1 + update_idx] += cards_count[idx];
+        .try_fold(0_u64, |total, count| total.checked_add(*count))
+        .ok_or_else(copies_overflow)
+}
+
+// Answers of both parts, reading one card at a time.
+// Only the copies won for the next cards are kept, at most as many as a card can win.
+pub fn stream_scratchcards(reader: impl BufRead) -> Result<(u64, u64), Error> {
+    // Copies won for the next cards, the next one first.
+    let mut copies = VecDeque::<u64>::new();
+    // (id, last line it wins a copy of) of the cards still winning copies, oldest first.
+    let mut pending = VecDeque::<(u32, usize)>::new();
+
+    let (points, total, lines) = fold_lines(
+        reader,
+        (0_u64, 0_u64, 0),
+        |(points, total, _), line, line_number| {
+            let card = get_card_info(line).map_err(|err| err.on_line(line_number))?;
+            let count = copies
+                .pop_front()
+                .unwrap_or(0)
+                .checked_add(1)
+                .ok_or_else(copies_overflow)?;
+            let wins = calculate_times_won_card(&card) as usize;
+
+            if copies.len() < wins {
+                copies.resize(wins, 0);
             }
-        });
+            for copy in copies.iter_mut().take(wins) {
+                *copy = copy.checked_add(count).ok_or_else(copies_overflow)?;
+            }
+
+            pending.retain(|(_, last)| *last > line_number);
+            if wins > 0 {
+                pending.push_back((card.id, line_number + wins));
+            }
+
+            let points = calculate_points_card(&card)
+                .and_then(|card_points| points.checked_add(card_points))
+                .ok_or_else(points_overflow)?;
+            let total = total.checked_add(count).ok_or_else(copies_overflow)?;
+
+            Ok((points, total, line_number))
+        },
+    )?;
 
-    cards_count.iter().sum()
+    match pending.iter().find(|(_, last)| *last > lines) {
+        Some((id, _)) => Err(Error::solve(
+            4,
+            format!("card {id} wins copies past the last card"),
+        )),
+        None => Ok((points, total)),
+    }
 }
 
-struct Card {
-    id: u32,
-    numbers: Vec<u32>,
-    wins: Vec<u32>,
+pub struct Card {
+    pub id: u32,
+    pub numbers: Vec<u32>,
+    pub wins: Vec<u32>,
 }
 
 fn calculate_times_won_card(card: &Card) -> u32 {
@@ -44,73 +131,99 @@ fn calculate_times_won_card(card: &Card) -> u32 {
         .count() as u32
 }
 
-fn calculate_points_card(card: &Card) -> u32 {
-    let won_count = card
-        .numbers
-        .iter()
-        .filter(|num| card.wins.contains(num))
-        .count() as u32;
-
-    match won_count {
-        0 => 0,
-        _ => 2u32.pow(won_count - 1),
+// `None` when the points overflow.
+fn calculate_points_card(card: &Card) -> Option<u64> {
+    match calculate_times_won_card(card) {
+        0 => Some(0),
+        won_count => 1_u64.checked_shl(won_count - 1),
     }
 }
 
-fn get_cards(input: &str) -> Vec<Card> {
-    input.lines().map(get_card_info).collect()
+fn get_cards(input: &str) -> Result<Vec<Card>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| get_card_info(line).map_err(|err| err.on_line(idx + 1)))
+        .collect()
 }
 
-fn get_card_info(input: &str) -> Card {
-    let mut id_and_numbers = input.split(':');
-    let id = id_and_numbers
-        .next()
-        .unwrap()
+// Numbers of `section`, a slice of the card line `input`.
+fn get_card_numbers(input: &str, section: &str) -> Result<Vec<u32>, Error> {
+    section
         .split(' ')
-        .filter(|c| !c.trim().is_empty())
-        .nth(1)
-        .unwrap()
-        .parse::<u32>()
-        .unwrap();
+        .filter(|c| !c.is_empty())
+        .map(|n| {
+            n.parse::<u32>()
+                .map_err(|_| Error::parse(4, column_of(input, n), format!("invalid number `{n}`")))
+        })
+        .collect()
+}
 
-    let num_and_wins = id_and_numbers
-        .next()
-        .unwrap()
-        .split('|')
-        .collect::<Vec<&str>>();
+// e.g. `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
+fn get_card_info(input: &str) -> Result<Card, Error> {
+    let (header, num_and_wins) = input
+        .split_once(':')
+        .ok_or_else(|| Error::parse(4, input.len() + 1, "expected ':' after the card id"))?;
 
-    let numbers = num_and_wins[0]
+    let id = match header
         .split(' ')
         .filter(|c| !c.is_empty())
-        .map(|n| n.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
+        .collect::<Vec<&str>>()[..]
+    {
+        ["Card", id] => id,
+        _ => return Err(Error::parse(4, 1, "expected `Card <id>`")),
+    };
 
-    let wins = num_and_wins[1]
-        .split(' ')
-        .filter(|c| !c.is_empty())
-        .map(|n| n.parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
+    let id = id
+        .parse::<u32>()
+        .map_err(|_| Error::parse(4, column_of(input, id), format!("invalid card id `{id}`")))?;
 
-    Card { id, numbers, wins }
+    let (numbers, wins) = num_and_wins.split_once('|').ok_or_else(|| {
+        Error::parse(
+            4,
+            column_of(input, num_and_wins),
+            "expected '|' separating numbers and winning numbers",
+        )
+    })?;
+
+    Ok(Card {
+        id,
+        numbers: get_card_numbers(input, numbers)?,
+        wins: get_card_numbers(input, wins)?,
+    })
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = Vec<Card>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        get_cards(input)
+    }
+
+    fn part1(cards: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        solve_scratchcards(cards)
+    }
+
+    fn part2(cards: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        solve_bonus_total_scratchcards(cards)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+    use crate::fixtures::example;
 
     #[test]
     fn test_get_card_info() {
         let expected_nums: Vec<u32> = vec![13, 32, 20, 16, 61];
         let expected_wins: Vec<u32> = vec![61, 30, 68, 82, 17, 32, 24, 19];
 
-        let card = get_card_info("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19");
+        let card = get_card_info("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19").unwrap();
 
         assert_eq!(card.id, 2);
         assert_eq!(card.numbers, expected_nums);
@@ -119,7 +232,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
 
     #[test]
     fn test_get_cards() {
-        let cards = get_cards(INPUT);
+        let cards = get_cards(&example(4, 1)).unwrap();
         let expected_nums: Vec<u32> = vec![13, 32, 20, 16, 61];
         let expected_wins: Vec<u32> = vec![61, 30, 68, 82, 17, 32, 24, 19];
 
@@ -137,7 +250,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         };
 
         let points = calculate_points_card(&card);
-        assert_eq!(points, 8);
+        assert_eq!(points, Some(8));
     }
 
     #[test]
@@ -149,7 +262,7 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         };
 
         let points = calculate_points_card(&card);
-        assert_eq!(points, 1);
+        assert_eq!(points, Some(1));
     }
 
     #[test]
@@ -161,18 +274,132 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         };
 
         let points = calculate_points_card(&card);
-        assert_eq!(points, 0);
+        assert_eq!(points, Some(0));
     }
 
     #[test]
     fn test_get_scratchcards_total() {
-        let total = solve_scratchcards(INPUT);
-        assert_eq!(total, 13);
+        let total = solve_scratchcards(&get_cards(&example(4, 1)).unwrap());
+        assert_eq!(total, Ok(13));
     }
 
     #[test]
     fn test_get_total_scratchcards_won() {
-        let total = solve_bonus_total_scratchcards(INPUT);
+        let total = solve_bonus_total_scratchcards(&get_cards(&example(4, 1)).unwrap()).unwrap();
         assert_eq!(total, 30);
     }
+
+    #[test]
+    fn test_get_card_info_missing_separator() {
+        let err = get_card_info("Card 17: 41 48 83 86 17 83 86")
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err,
+            Error::parse(4, 9, "expected '|' separating numbers and winning numbers")
+        );
+    }
+
+    #[test]
+    fn test_get_cards_invalid_number() {
+        let err = get_cards("Card 1: 41 | 83\nCard 2: 4x | 83").err().unwrap();
+
+        assert_eq!(err, Error::parse(4, 9, "invalid number `4x`").on_line(2));
+    }
+
+    #[test]
+    fn test_stream_scratchcards() {
+        assert_eq!(stream_scratchcards(example(4, 1).as_bytes()), Ok((13, 30)));
+    }
+
+    #[test]
+    fn test_stream_scratchcards_past_last_card() {
+        let input = "Card 1: 41 | 41\nCard 2: 41 48 | 41 48\nCard 3: 1 | 2";
+
+        assert_eq!(
+            stream_scratchcards(input.as_bytes()),
+            Err(Error::solve(4, "card 2 wins copies past the last card"))
+        );
+        assert_eq!(
+            stream_scratchcards(input.as_bytes()).err(),
+            solve_bonus_total_scratchcards(&get_cards(input).unwrap()).err()
+        );
+    }
+
+    #[test]
+    fn test_copies_overflow() {
+        // Every card wins a copy of the next two, so the copies grow like Fibonacci numbers.
+        let mut lines = (1..=98)
+            .map(|id| format!("Card {id}: 1 2 | 1 2"))
+            .collect::<Vec<String>>();
+        lines.push("Card 99: 1 | 2".to_owned());
+        lines.push("Card 100: 1 | 2".to_owned());
+        let input = lines.join("\n");
+        let overflow = Error::solve(4, "number of scratchcards overflows");
+
+        assert_eq!(
+            solve_bonus_total_scratchcards(&get_cards(&input).unwrap()),
+            Err(overflow.clone())
+        );
+        assert_eq!(stream_scratchcards(input.as_bytes()), Err(overflow));
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        let numbers = (1..=70)
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        let input = format!("Card 1: {numbers} | {numbers}");
+
+        assert_eq!(
+            solve_scratchcards(&get_cards(&input).unwrap()),
+            Err(Error::solve(4, "total points overflow"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn join(numbers: &[u32]) -> String {
+        numbers
+            .iter()
+            .map(|number| format!("{number:>2}"))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    proptest! {
+        #[test]
+        fn test_card_round_trip(
+            id in 0..100_000_u32,
+            numbers in prop::collection::vec(0..100_u32, 0..10),
+            wins in prop::collection::vec(0..100_u32, 0..25),
+        ) {
+            let line = format!("Card {id:>3}: {} | {}", join(&numbers), join(&wins));
+            let card = get_card_info(&line).unwrap();
+
+            prop_assert_eq!(card.id, id);
+            prop_assert_eq!(card.numbers, numbers);
+            prop_assert_eq!(card.wins, wins);
+        }
+
+        #[test]
+        fn test_card_never_panics(line in "\\PC*") {
+            if let Err(Error::Parse { column, .. }) = get_card_info(&line) {
+                prop_assert!((1..=line.len() + 1).contains(&column));
+            }
+        }
+
+        #[test]
+        fn test_card_like_never_panics(line in "(Card|card|) {0,3}[0-9]{0,3}:?[ 0-9|a]{0,20}") {
+            if let Err(Error::Parse { column, .. }) = get_card_info(&line) {
+                prop_assert!((1..=line.len() + 1).contains(&column));
+            }
+        }
+    }
 }
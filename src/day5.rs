@@ -0,0 +1,364 @@
+use crate::error::{column_of, Error};
+use crate::solution::Solution;
+use std::ops::Range;
+
+// One line of a map: `length` values from `source` on are sent to `destination` on.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RangeMapEntry {
+    pub destination: u64,
+    pub source: u64,
+    pub length: u64,
+}
+
+impl RangeMapEntry {
+    fn sources(&self) -> Range<u64> {
+        self.source..self.source + self.length
+    }
+
+    fn shift(&self, value: u64) -> u64 {
+        value - self.source + self.destination
+    }
+}
+
+// A `from-to-to map:` block. Values outside every entry are left as they are.
+pub struct RangeMap {
+    pub from: String,
+    pub to: String,
+    // Sorted by source. When entries overlap, the first one wins.
+    pub entries: Vec<RangeMapEntry>,
+}
+
+impl RangeMap {
+    pub fn new(from: &str, to: &str, mut entries: Vec<RangeMapEntry>) -> RangeMap {
+        // Entries of length 0 send nothing anywhere.
+        entries.retain(|entry| entry.length > 0);
+        entries.sort_by_key(|entry| entry.source);
+
+        RangeMap {
+            from: from.to_owned(),
+            to: to.to_owned(),
+            entries,
+        }
+    }
+
+    pub fn get(&self, value: u64) -> u64 {
+        self.entries
+            .iter()
+            .find(|entry| entry.sources().contains(&value))
+            .map_or(value, |entry| entry.shift(value))
+    }
+
+    // Sends whole ranges through the map, splitting them on the entry boundaries.
+    pub fn get_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut result = Vec::new();
+
+        for range in ranges.iter().filter(|range| !range.is_empty()) {
+            // Start of the part of `range` not mapped yet.
+            let mut cursor = range.start;
+
+            for entry in &self.entries {
+                let sources = entry.sources();
+
+                if sources.end <= cursor {
+                    continue;
+                }
+                if sources.start >= range.end {
+                    break;
+                }
+
+                if sources.start > cursor {
+                    result.push(cursor..sources.start);
+                    cursor = sources.start;
+                }
+
+                let end = sources.end.min(range.end);
+                result.push(entry.shift(cursor)..entry.shift(end - 1) + 1);
+                cursor = end;
+            }
+
+            if cursor < range.end {
+                result.push(cursor..range.end);
+            }
+        }
+
+        result
+    }
+}
+
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    // Chained, from `seed` to the last category.
+    pub maps: Vec<RangeMap>,
+}
+
+impl Almanac {
+    pub fn location(&self, seed: u64) -> u64 {
+        self.maps.iter().fold(seed, |value, map| map.get(value))
+    }
+
+    // The seeds read as `start length` pairs.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<u64>>, Error> {
+        self.seeds
+            .chunks(2)
+            .map(|pair| match pair {
+                [start, length] => start
+                    .checked_add(*length)
+                    .map(|end| *start..end)
+                    .ok_or_else(|| {
+                        Error::solve(5, format!("seed range {start} {length} overflows"))
+                    }),
+                _ => Err(Error::solve(5, "expected seeds as `start length` pairs")),
+            })
+            .collect()
+    }
+}
+
+fn parse_numbers(line: &str, numbers: &str) -> Result<Vec<u64>, Error> {
+    numbers
+        .split(' ')
+        .filter(|n| !n.is_empty())
+        .map(|n| {
+            n.parse::<u64>()
+                .map_err(|_| Error::parse(5, column_of(line, n), format!("invalid number `{n}`")))
+        })
+        .collect()
+}
+
+// e.g. `50 98 2`
+fn parse_map_entry(line: &str) -> Result<RangeMapEntry, Error> {
+    let (destination, source, length) = match parse_numbers(line, line)?[..] {
+        [destination, source, length] => (destination, source, length),
+        _ => {
+            return Err(Error::parse(
+                5,
+                1,
+                "expected `<destination> <source> <length>`",
+            ))
+        }
+    };
+
+    if source.checked_add(length).is_none() || destination.checked_add(length).is_none() {
+        return Err(Error::parse(5, 1, "range overflows"));
+    }
+
+    Ok(RangeMapEntry {
+        destination,
+        source,
+        length,
+    })
+}
+
+// e.g. `seed-to-soil map:`
+fn parse_map_header(line: &str) -> Result<(&str, &str), Error> {
+    line.strip_suffix(" map:")
+        .and_then(|name| name.split_once("-to-"))
+        .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+        .ok_or_else(|| Error::parse(5, 1, "expected `<from>-to-<to> map:`"))
+}
+
+fn get_almanac(input: &str) -> Result<Almanac, Error> {
+    let mut lines = input.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+
+    let seeds_line = lines.next().map_or("", |(_, line)| line);
+    let seeds = seeds_line
+        .strip_prefix("seeds:")
+        .ok_or_else(|| Error::parse(5, 1, "expected `seeds:`"))
+        .and_then(|seeds| parse_numbers(seeds_line, seeds))?;
+
+    let mut maps = Vec::new();
+    // (line number, from, to, entries) of the map being read.
+    let mut current: Option<(usize, &str, &str, Vec<RangeMapEntry>)> = None;
+
+    for (line_number, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+        if line.ends_with(':') {
+            let (from, to) = parse_map_header(line).map_err(|err| err.on_line(line_number))?;
+            maps.extend(current.replace((line_number, from, to, Vec::new())));
+        } else if let Some((_, _, _, entries)) = &mut current {
+            entries.push(parse_map_entry(line).map_err(|err| err.on_line(line_number))?);
+        } else {
+            return Err(Error::parse(5, 1, "expected a map header").on_line(line_number));
+        }
+    }
+    maps.extend(current);
+
+    // Every map must start where the previous one ended.
+    let mut category = "seed";
+    let maps = maps
+        .into_iter()
+        .map(|(line_number, from, to, entries)| {
+            if from != category {
+                return Err(
+                    Error::parse(5, 1, format!("expected a map from `{category}`"))
+                        .on_line(line_number),
+                );
+            }
+
+            category = to;
+            Ok(RangeMap::new(from, to, entries))
+        })
+        .collect::<Result<Vec<RangeMap>, Error>>()?;
+
+    Ok(Almanac { seeds, maps })
+}
+
+pub fn get_lowest_location(almanac: &Almanac) -> Result<u64, Error> {
+    almanac
+        .seeds
+        .iter()
+        .map(|seed| almanac.location(*seed))
+        .min()
+        .ok_or_else(|| Error::solve(5, "no seed to plant"))
+}
+
+pub fn get_lowest_location_ranges(almanac: &Almanac) -> Result<u64, Error> {
+    almanac
+        .maps
+        .iter()
+        .fold(almanac.seed_ranges()?, |ranges, map| {
+            map.get_ranges(&ranges)
+        })
+        .iter()
+        .map(|range| range.start)
+        .min()
+        .ok_or_else(|| Error::solve(5, "no seed to plant"))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = Almanac;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        get_almanac(input)
+    }
+
+    fn part1(almanac: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        get_lowest_location(almanac)
+    }
+
+    fn part2(almanac: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        get_lowest_location_ranges(almanac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::example;
+
+    fn seed_to_soil() -> RangeMap {
+        RangeMap::new(
+            "seed",
+            "soil",
+            vec![
+                RangeMapEntry {
+                    destination: 50,
+                    source: 98,
+                    length: 2,
+                },
+                RangeMapEntry {
+                    destination: 52,
+                    source: 50,
+                    length: 48,
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn test_part1_example() {
+        let almanac = Day5::parse(&example(5, 1)).unwrap();
+
+        assert_eq!(almanac.maps.len(), 7);
+        assert_eq!(Day5::part1(&almanac), Ok(35));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        let almanac = Day5::parse(&example(5, 1)).unwrap();
+
+        assert_eq!(Day5::part2(&almanac), Ok(46));
+    }
+
+    #[test]
+    fn test_range_map_get() {
+        let map = seed_to_soil();
+
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(14), 14);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(100), 100);
+    }
+
+    #[test]
+    fn test_get_ranges_splits_on_boundaries() {
+        let map = seed_to_soil();
+
+        assert_eq!(
+            map.get_ranges(&[40..60, 95..105]),
+            vec![40..50, 52..62, 97..100, 50..52, 100..105]
+        );
+    }
+
+    #[test]
+    fn test_get_ranges_matches_get() {
+        let map = seed_to_soil();
+        let mut values = map
+            .get_ranges(&[0..100, 100..120])
+            .into_iter()
+            .flatten()
+            .collect::<Vec<u64>>();
+        let mut expected = (0..120).map(|value| map.get(value)).collect::<Vec<u64>>();
+        values.sort();
+        expected.sort();
+
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn test_billions_of_seeds() {
+        let almanac = Day5::parse(&example(5, 1).replace("79 14 55 13", "0 4000000000")).unwrap();
+
+        assert_eq!(Day5::part2(&almanac), Ok(0));
+    }
+
+    #[test]
+    fn test_broken_chain() {
+        let input = example(5, 1).replace("soil-to-fertilizer", "sand-to-fertilizer");
+        let err = Day5::parse(&input).err().unwrap();
+
+        assert_eq!(
+            err,
+            Error::parse(5, 1, "expected a map from `soil`").on_line(7)
+        );
+    }
+
+    #[test]
+    fn test_invalid_entry() {
+        let err = Day5::parse("seeds: 1\n\nseed-to-soil map:\n50 9x 2")
+            .err()
+            .unwrap();
+
+        assert_eq!(err, Error::parse(5, 4, "invalid number `9x`").on_line(4));
+    }
+
+    #[test]
+    fn test_empty_entry() {
+        let almanac = Day5::parse("seeds: 1 10\n\nseed-to-soil map:\n50 5 0").unwrap();
+
+        assert_eq!(Day5::part1(&almanac), Ok(1));
+        assert_eq!(Day5::part2(&almanac), Ok(1));
+    }
+
+    #[test]
+    fn test_odd_seed_count() {
+        let almanac = Day5::parse("seeds: 1 2 3").unwrap();
+
+        assert_eq!(
+            Day5::part2(&almanac),
+            Err(Error::solve(5, "expected seeds as `start length` pairs"))
+        );
+    }
+}
@@ -1,67 +1,187 @@
-fn parse_record_entry(input: &str) -> (Vec<u32>, Vec<u32>) {
-    let (times_str, distances_str) = input.trim().split_once('\n').unwrap();
+use crate::error::{column_of, Error};
+use crate::solution::Solution;
 
-    let times = times_str
-        .split(' ')
-        .filter_map(|c| c.trim().parse::<u32>().ok())
-        .collect::<Vec<u32>>();
+pub struct Races {
+    // One entry per race, as printed.
+    pub times: Vec<u32>,
+    pub distances: Vec<u32>,
+    // The `Time:` and `Distance:` lines, only read as a single race by part 2
+    // since the joined numbers may not fit in 64 bits.
+    lines: (String, String),
+}
+
+const TIME_LABEL: &str = "Time:";
+const DISTANCE_LABEL: &str = "Distance:";
+
+// The `Time:` and `Distance:` lines, checking their label.
+fn get_record_lines(input: &str) -> Result<(&str, &str), Error> {
+    let mut lines = input.lines();
+    let mut next_line = |label: &str, line_number: usize| {
+        lines
+            .next()
+            .filter(|line| line.starts_with(label))
+            .ok_or_else(|| Error::parse(6, 1, format!("expected `{label}`")).on_line(line_number))
+    };
 
-    let distances = distances_str
+    Ok((next_line(TIME_LABEL, 1)?, next_line(DISTANCE_LABEL, 2)?))
+}
+
+fn parse_record_values(line: &str, label: &str, line_number: usize) -> Result<Vec<u32>, Error> {
+    line[label.len()..]
         .split(' ')
-        .filter_map(|c| c.trim().parse::<u32>().ok())
-        .collect::<Vec<u32>>();
+        .filter(|c| !c.is_empty())
+        .map(|c| {
+            c.parse::<u32>().map_err(|_| {
+                Error::parse(6, column_of(line, c), format!("invalid number `{c}`"))
+                    .on_line(line_number)
+            })
+        })
+        .collect()
+}
 
-    (times, distances)
+fn parse_record_entry(input: &str) -> Result<(Vec<u32>, Vec<u32>), Error> {
+    let (times_str, distances_str) = get_record_lines(input)?;
+
+    let times = parse_record_values(times_str, TIME_LABEL, 1)?;
+    let distances = parse_record_values(distances_str, DISTANCE_LABEL, 2)?;
+
+    if times.len() != distances.len() {
+        return Err(Error::parse(
+            6,
+            1,
+            format!(
+                "expected {} distances, found {}",
+                times.len(),
+                distances.len()
+            ),
+        )
+        .on_line(2));
+    }
+
+    Ok((times, distances))
 }
 
-fn parse_record_entry_single(input: &str) -> (u64, u64) {
-    let (times_str, distances_str) = input.trim().split_once('\n').unwrap();
-    let (_, times) = times_str.split_once(' ').unwrap();
-    let (_, distances) = distances_str.split_once(' ').unwrap();
+// The single race read when ignoring the spaces.
+fn parse_single_race(times: &str, distances: &str) -> Result<(u64, u64), Error> {
+    let parse_single = |line: &str, label: &str, line_number: usize| {
+        line[label.len()..]
+            .replace(' ', "")
+            .parse::<u64>()
+            .map_err(|_| {
+                Error::parse(
+                    6,
+                    label.len() + 1,
+                    "expected digits forming a number that fits in 64 bits",
+                )
+                .on_line(line_number)
+            })
+    };
 
-    (
-        times.replace(" ", "").trim().parse::<u64>().unwrap(),
-        distances.replace(" ", "").trim().parse::<u64>().unwrap(),
-    )
+    Ok((
+        parse_single(times, TIME_LABEL, 1)?,
+        parse_single(distances, DISTANCE_LABEL, 2)?,
+    ))
 }
 
+// Holding for `hold` travels `hold * (time - hold)`, which grows up to half the time.
+// The first hold beating the record is found by bisection, the winning ones being
+// symmetric around the middle.
 fn possible_wins_for_record(distance_to_beat: u64, time_max: u64) -> u64 {
-    (1..time_max - 1)
-        .map(|time_hold| time_hold * (time_max - time_hold))
-        .filter(|distance| distance_to_beat < *distance)
-        .count() as u64
+    let (time, distance) = (time_max as u128, distance_to_beat as u128);
+    let (mut low, mut high) = (0, time / 2);
+
+    if high * (time - high) <= distance {
+        return 0;
+    }
+
+    while low < high {
+        let mid = (low + high) / 2;
+        match mid * (time - mid) > distance {
+            true => high = mid,
+            false => low = mid + 1,
+        }
+    }
+
+    (time + 1 - 2 * low) as u64
+}
+
+fn get_races(input: &str) -> Result<Races, Error> {
+    let (times, distances) = parse_record_entry(input)?;
+    let (time_line, distance_line) = get_record_lines(input)?;
+
+    Ok(Races {
+        times,
+        distances,
+        lines: (time_line.to_owned(), distance_line.to_owned()),
+    })
+}
+
+pub fn get_wins_product(races: &Races) -> Result<u64, Error> {
+    races
+        .times
+        .iter()
+        .zip(&races.distances)
+        .map(|(&time, &distance)| possible_wins_for_record(distance as u64, time as u64))
+        .try_fold(1_u64, |product, wins| product.checked_mul(wins))
+        .ok_or_else(|| Error::solve(6, "product of the ways to win overflows"))
 }
 
-pub fn get_wins_product(input: &str) -> u64 {
-    let (times, distances) = parse_record_entry(input);
-    assert_eq!(times.len(), distances.len());
+pub fn get_possible_wins_single(races: &Races) -> Result<u64, Error> {
+    let (time, distance) = parse_single_race(&races.lines.0, &races.lines.1)?;
 
-    (0..times.len())
-        .map(|idx| possible_wins_for_record(distances[idx] as u64, times[idx] as u64))
-        .product()
+    Ok(possible_wins_for_record(distance, time))
 }
 
-pub fn get_possible_wins_single(input: &str) -> u64 {
-    let (time, distance) = parse_record_entry_single(input);
+pub struct Day6;
 
-    possible_wins_for_record(distance, time)
+impl Solution for Day6 {
+    type Input<'a> = Races;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        get_races(input)
+    }
+
+    fn part1(races: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        get_wins_product(races)
+    }
+
+    fn part2(races: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        get_possible_wins_single(races)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixtures::example;
 
-    // Line 1: Seeds needed to be planted
-    const INPUT: &str = "Time:      7  15   30
-Distance:  9  40  200";
     const INPUT_BONUS: &str = "Time:        40     92     97     90
 Distance:   215   1064   1505   1100";
 
     #[test]
     fn test_load() {
-        let product = get_wins_product(INPUT);
+        let product = get_wins_product(&get_races(&example(6, 1)).unwrap());
 
-        assert_eq!(product, 288);
+        assert_eq!(product, Ok(288));
+    }
+
+    #[test]
+    fn test_joined_race_too_large_only_fails_part2() {
+        let input = "Time:      4000000000  4000000000\nDistance:  4000000000  4000000000";
+        let races = Day6::parse(input).unwrap();
+
+        assert_eq!(Day6::part1(&races), Ok(3999999997 * 3999999997));
+        assert_eq!(
+            Day6::part2(&races),
+            Err(Error::parse(
+                6,
+                6,
+                "expected digits forming a number that fits in 64 bits"
+            )
+            .on_line(1))
+        );
     }
 
     #[test]
@@ -78,7 +198,7 @@ Distance:   215   1064   1505   1100";
 
     #[test]
     fn test_parse_record_entry() {
-        let (times, distances) = parse_record_entry(INPUT);
+        let (times, distances) = parse_record_entry(&example(6, 1)).unwrap();
 
         let expected_times = vec![7, 15, 30];
         let expected_distances = vec![9, 40, 200];
@@ -89,7 +209,9 @@ Distance:   215   1064   1505   1100";
 
     #[test]
     fn test_parse_record_entry_single() {
-        let (time, distance) = parse_record_entry_single(INPUT);
+        let input = example(6, 1);
+        let (times, distances) = get_record_lines(&input).unwrap();
+        let (time, distance) = parse_single_race(times, distances).unwrap();
 
         assert_eq!(time, 71530);
         assert_eq!(distance, 940200);
@@ -97,9 +219,73 @@ Distance:   215   1064   1505   1100";
 
     #[test]
     fn test_get_wins_product_2() {
-        let (time, distance) = parse_record_entry_single(INPUT_BONUS);
-        let product = possible_wins_for_record(distance, time);
+        let product = get_possible_wins_single(&get_races(INPUT_BONUS).unwrap());
+
+        assert_eq!(product, Ok(28545089));
+    }
+
+    #[test]
+    fn test_parse_record_entry_missing_distances() {
+        let err = parse_record_entry("Time:      7  15   30\nDistance:  9  40")
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err,
+            Error::parse(6, 1, "expected 3 distances, found 2").on_line(2)
+        );
+    }
+
+    #[test]
+    fn test_parse_record_entry_invalid_number() {
+        let err = parse_record_entry("Time:      7  1S   30\nDistance:  9  40  200")
+            .err()
+            .unwrap();
+
+        assert_eq!(err, Error::parse(6, 15, "invalid number `1S`"));
+    }
+}
+
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn races() -> impl Strategy<Value = Vec<(u32, u32)>> {
+        prop::collection::vec((0..10_000_u32, 0..10_000_u32), 0..8)
+    }
+
+    fn record(label: &str, values: &[u32]) -> String {
+        values.iter().fold(label.to_owned(), |line, value| {
+            format!("{line}  {value:>4}")
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_record_entry_round_trip(races in races()) {
+            let (times, distances): (Vec<u32>, Vec<u32>) = races.into_iter().unzip();
+            let input = format!("{}\n{}", record(TIME_LABEL, &times), record(DISTANCE_LABEL, &distances));
+
+            prop_assert_eq!(parse_record_entry(&input).unwrap(), (times.clone(), distances.clone()));
+
+            let concatenated = |values: &[u32]| {
+                values.iter().map(u32::to_string).collect::<String>().parse::<u64>().ok()
+            };
+            prop_assert_eq!(
+                get_record_lines(&input).and_then(|(times, distances)| parse_single_race(times, distances)).ok(),
+                concatenated(&times).zip(concatenated(&distances))
+            );
+        }
+
+        #[test]
+        fn test_record_entry_never_panics(input in "\\PC*(\n\\PC*)?") {
+            let _ = Day6::parse(&input).map(|races| (Day6::part1(&races), Day6::part2(&races)));
+        }
 
-        assert_eq!(product, 28545089);
+        #[test]
+        fn test_record_entry_like_never_panics(input in "Time:[ 0-9a]{0,12}\nDistance:[ 0-9a]{0,12}") {
+            let _ = Day6::parse(&input).map(|races| (Day6::part1(&races), Day6::part2(&races)));
+        }
     }
 }
@@ -0,0 +1,320 @@
+use crate::error::{column_of, Error};
+use crate::solution::Solution;
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+const CARDS: &str = "23456789TJQKA";
+const JOKER: u8 = b'J';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    // From the sizes of the groups of equal cards, largest first.
+    fn from_groups(groups: &[u8]) -> HandType {
+        match groups {
+            [5, ..] => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+// Sizes of the groups of equal cards, largest first, leaving out `skipped`.
+fn groups(cards: &[u8; 5], skipped: Option<u8>) -> Vec<u8> {
+    let mut counts = [0_u8; CARDS.len()];
+
+    for card in cards.iter().filter(|card| Some(**card) != skipped) {
+        counts[CARDS.bytes().position(|c| c == *card).unwrap()] += 1;
+    }
+
+    let mut groups = counts
+        .into_iter()
+        .filter(|count| *count > 0)
+        .collect::<Vec<u8>>();
+    groups.sort_by(|a, b| b.cmp(a));
+    groups
+}
+
+// How hands are ranked. The comparison itself is shared by every rule set:
+// first the type, then the strength of each card in order.
+pub trait Rules {
+    fn hand_type(cards: &[u8; 5]) -> HandType;
+    fn card_strength(card: u8) -> usize;
+}
+
+// Part 1.
+pub struct Standard;
+
+impl Rules for Standard {
+    fn hand_type(cards: &[u8; 5]) -> HandType {
+        HandType::from_groups(&groups(cards, None))
+    }
+
+    fn card_strength(card: u8) -> usize {
+        CARDS.bytes().position(|c| c == card).unwrap()
+    }
+}
+
+// Part 2: `J` cards are jokers, acting as whatever makes the strongest type
+// but being the weakest card on their own.
+pub struct Jokers;
+
+impl Rules for Jokers {
+    fn hand_type(cards: &[u8; 5]) -> HandType {
+        let jokers = cards.iter().filter(|card| **card == JOKER).count() as u8;
+        let mut groups = groups(cards, Some(JOKER));
+
+        // Jokers always join the largest group.
+        match groups.first_mut() {
+            Some(largest) => *largest += jokers,
+            None => groups.push(jokers),
+        }
+
+        HandType::from_groups(&groups)
+    }
+
+    fn card_strength(card: u8) -> usize {
+        match card {
+            JOKER => 0,
+            _ => Standard::card_strength(card) + 1,
+        }
+    }
+}
+
+pub struct Hand<R: Rules> {
+    pub cards: [u8; 5],
+    pub hand_type: HandType,
+    rules: PhantomData<R>,
+}
+
+impl<R: Rules> Hand<R> {
+    // Fails on any card outside of `CARDS`, which the rules cannot rank.
+    pub fn new(cards: [u8; 5]) -> Result<Hand<R>, Error> {
+        if let Some(idx) = cards
+            .iter()
+            .position(|card| !CARDS.as_bytes().contains(card))
+        {
+            return Err(Error::parse(
+                7,
+                idx + 1,
+                format!("invalid card `{}`", cards[idx].escape_ascii()),
+            ));
+        }
+
+        Ok(Hand {
+            cards,
+            hand_type: R::hand_type(&cards),
+            rules: PhantomData,
+        })
+    }
+}
+
+impl<R: Rules> Ord for Hand<R> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.hand_type.cmp(&other.hand_type).then_with(|| {
+            self.cards
+                .iter()
+                .map(|card| R::card_strength(*card))
+                .cmp(other.cards.iter().map(|card| R::card_strength(*card)))
+        })
+    }
+}
+
+impl<R: Rules> PartialOrd for Hand<R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: Rules> PartialEq for Hand<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<R: Rules> Eq for Hand<R> {}
+
+pub struct Play {
+    pub cards: [u8; 5],
+    pub bid: u64,
+}
+
+// e.g. `32T3K 765`
+fn get_play(line: &str) -> Result<Play, Error> {
+    let (cards_str, bid) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::parse(7, line.len() + 1, "expected `<cards> <bid>`"))?;
+
+    if let Some((idx, card)) = cards_str.char_indices().find(|(_, c)| !CARDS.contains(*c)) {
+        return Err(Error::parse(7, idx + 1, format!("invalid card `{card}`")));
+    }
+
+    let cards = <[u8; 5]>::try_from(cards_str.as_bytes())
+        .map_err(|_| Error::parse(7, 1, format!("expected 5 cards, found {}", cards_str.len())))?;
+
+    let bid = bid
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| Error::parse(7, column_of(line, bid), format!("invalid bid `{bid}`")))?;
+
+    Ok(Play { cards, bid })
+}
+
+fn get_plays(input: &str) -> Result<Vec<Play>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| get_play(line).map_err(|err| err.on_line(idx + 1)))
+        .collect()
+}
+
+// Sum of the bids, each multiplied by the rank of its hand, the weakest being 1.
+pub fn get_total_winnings<R: Rules>(plays: &[Play]) -> Result<u64, Error> {
+    let mut hands = plays
+        .iter()
+        .map(|play| Hand::<R>::new(play.cards).map(|hand| (hand, play.bid)))
+        .collect::<Result<Vec<(Hand<R>, u64)>, Error>>()?;
+    hands.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    (1_u64..)
+        .zip(&hands)
+        .try_fold(0_u64, |total, (rank, (_, bid))| {
+            rank.checked_mul(*bid)
+                .and_then(|winnings| total.checked_add(winnings))
+        })
+        .ok_or_else(|| Error::solve(7, "total winnings overflow"))
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = Vec<Play>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        get_plays(input)
+    }
+
+    fn part1(plays: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        get_total_winnings::<Standard>(plays)
+    }
+
+    fn part2(plays: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        get_total_winnings::<Jokers>(plays)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::example;
+
+    fn hand<R: Rules>(cards: &str) -> Hand<R> {
+        Hand::new(cards.as_bytes().try_into().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_part1_example() {
+        let input = Day7::parse(&example(7, 1)).unwrap();
+
+        assert_eq!(Day7::part1(&input), Ok(6440));
+    }
+
+    #[test]
+    fn test_part2_example() {
+        let input = Day7::parse(&example(7, 1)).unwrap();
+
+        assert_eq!(Day7::part2(&input), Ok(5905));
+    }
+
+    #[test]
+    fn test_standard_hand_types() {
+        let types = [
+            "AAAAA", "AA8AA", "23332", "TTT98", "23432", "A23A4", "23456",
+        ]
+        .map(|cards| hand::<Standard>(cards).hand_type);
+
+        assert_eq!(
+            types,
+            [
+                HandType::FiveOfAKind,
+                HandType::FourOfAKind,
+                HandType::FullHouse,
+                HandType::ThreeOfAKind,
+                HandType::TwoPair,
+                HandType::OnePair,
+                HandType::HighCard,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_standard_ordering() {
+        assert!(hand::<Standard>("33332") > hand::<Standard>("2AAAA"));
+        assert!(hand::<Standard>("77888") > hand::<Standard>("77788"));
+        assert!(hand::<Standard>("KK677") > hand::<Standard>("KTJJT"));
+    }
+
+    #[test]
+    fn test_joker_hand_types() {
+        assert_eq!(hand::<Jokers>("QJJQ2").hand_type, HandType::FourOfAKind);
+        assert_eq!(hand::<Jokers>("JJJJJ").hand_type, HandType::FiveOfAKind);
+        assert_eq!(hand::<Jokers>("T55J5").hand_type, HandType::FourOfAKind);
+        assert_eq!(hand::<Jokers>("2345J").hand_type, HandType::OnePair);
+    }
+
+    #[test]
+    fn test_joker_ordering() {
+        assert!(hand::<Jokers>("JKKK2") < hand::<Jokers>("QQQQ2"));
+        assert!(hand::<Jokers>("KTJJT") > hand::<Jokers>("QQQJA"));
+        assert!(hand::<Jokers>("JJJJJ") < hand::<Jokers>("22222"));
+    }
+
+    #[test]
+    fn test_invalid_card() {
+        let err = Day7::parse("32T3K 765\n32X3K 684").err().unwrap();
+
+        assert_eq!(err, Error::parse(7, 3, "invalid card `X`").on_line(2));
+    }
+
+    #[test]
+    fn test_hand_invalid_card() {
+        assert_eq!(
+            Hand::<Standard>::new(*b"XXXXX").err(),
+            Some(Error::parse(7, 1, "invalid card `X`"))
+        );
+        assert!(Hand::<Jokers>::new(*b"AKQ\xff2").is_err());
+    }
+
+    #[test]
+    fn test_total_winnings_overflow() {
+        let input = Day7::parse("32T3K 18446744073709551615\nKK677 2").unwrap();
+
+        assert_eq!(
+            Day7::part1(&input),
+            Err(Error::solve(7, "total winnings overflow"))
+        );
+    }
+
+    #[test]
+    fn test_wrong_card_count() {
+        let err = Day7::parse("32T3 765").err().unwrap();
+
+        assert_eq!(err, Error::parse(7, 1, "expected 5 cards, found 4"));
+    }
+}
@@ -1,24 +1,24 @@
+use crate::error::{column_of, Error};
+use crate::solution::Solution;
+use log::debug;
 use num::Integer;
-use std::io::Write;
-use std::ops::{Deref, DerefMut, Index};
-use std::str::FromStr;
 
-type NodePosition = Option<usize>;
+pub type NodePosition = Option<usize>;
 
 #[derive(PartialEq, Debug)]
-struct Node {
-    id: String,
-    left: NodePosition,
-    right: NodePosition,
+pub struct Node {
+    pub id: String,
+    pub left: NodePosition,
+    pub right: NodePosition,
 }
 
-struct Map {
-    moves: String,
-    nodes: Vec<Node>,
-    start: String,
+pub struct Map {
+    pub moves: String,
+    pub nodes: Vec<Node>,
+    pub start: String,
 }
 
-struct MapIterator<'a> {
+pub struct MapIterator<'a> {
     pattern: &'a str,
     nodes: &'a Vec<Node>,
     current_pattern_index: usize,
@@ -26,12 +26,17 @@ struct MapIterator<'a> {
 }
 
 impl Map {
-    pub fn iter(&self) -> MapIterator {
+    pub fn iter(&self) -> MapIterator<'_> {
+        self.iter_from(&self.start)
+    }
+
+    // Walks the map from any node instead of the starting one.
+    pub fn iter_from(&self, start: &str) -> MapIterator<'_> {
         MapIterator {
             pattern: &self.moves,
             nodes: &self.nodes,
             current_pattern_index: 0,
-            current_node: self.nodes.iter().position(|node| node.id == self.start),
+            current_node: self.nodes.iter().position(|node| node.id == start),
         }
     }
 
@@ -46,41 +51,65 @@ impl Map {
 
     // Links a node with present nodes in the map.
     // A node may link to itself.
+    // Unknown nodes are left unlinked.
     pub fn link(&mut self, id: &str, left: &str, right: &str) {
-        let node2update = self.nodes.iter().position(|node| node.id == id).unwrap();
-
-        self.nodes[node2update].left = self.nodes.iter().position(|node| node.id == left);
-        self.nodes[node2update].right = self.nodes.iter().position(|node| node.id == right);
+        if let Some(node2update) = self.nodes.iter().position(|node| node.id == id) {
+            self.nodes[node2update].left = self.nodes.iter().position(|node| node.id == left);
+            self.nodes[node2update].right = self.nodes.iter().position(|node| node.id == right);
+        }
     }
 
-    fn new(input: &str, start: &str) -> Map {
-        let (moves, move_map) = input.trim().split_once('\n').unwrap();
+    fn new(input: &str, start: &str) -> Result<Map, Error> {
+        let mut lines = input.lines().enumerate();
+        let moves = lines.next().map_or("", |(_, moves)| moves.trim());
+
+        if moves.is_empty() {
+            return Err(Error::parse(8, 1, "expected the `L` and `R` moves"));
+        }
+
+        if let Some(idx) = moves.find(|c| c != 'L' && c != 'R') {
+            return Err(Error::parse(
+                8,
+                column_of(input, moves) + idx,
+                "expected only `L` and `R` moves",
+            ));
+        }
 
         let mut map = Map {
-            moves: moves.trim().to_owned(),
+            moves: moves.to_owned(),
             nodes: vec![],
             start: start.to_owned(),
         };
 
+        let entries = lines
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                parse_map_entry(line)
+                    .map(|entry| (idx + 1, line, entry))
+                    .map_err(|err| err.on_line(idx + 1))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
         // Push existing nodes
-        move_map
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .for_each(|line| {
-                let (id, _) = line.trim().split_once(' ').unwrap();
-                map.push(id);
-            });
+        entries.iter().for_each(|(_, _, (id, _, _))| map.push(id));
 
         // Link nodes
-        move_map
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .map(parse_map_entry)
-            .for_each(|(id, left, right)| {
-                map.link(id.as_ref(), left.as_ref(), right.as_ref());
-            });
-
-        map
+        for (line_number, line, (id, left, right)) in &entries {
+            for neighbour in [left, right] {
+                if !map.nodes.iter().any(|node| node.id == *neighbour) {
+                    return Err(Error::parse(
+                        8,
+                        column_of(line, neighbour),
+                        format!("unknown node `{neighbour}`"),
+                    )
+                    .on_line(*line_number));
+                }
+            }
+
+            map.link(id, left, right);
+        }
+
+        Ok(map)
     }
 }
 
@@ -115,54 +144,97 @@ impl<'a> Iterator for MapIterator<'a> {
 }
 
 // Returns (id, left, right)
-fn parse_map_entry(input: &str) -> (String, String, String) {
-    let pattern = r"\s*(?P<idGroup>\w+)\s*=\s*\((?P<leftGroup>\w+)\s*,\s*(?P<rightGroup>\w+)\)";
+fn parse_map_entry(input: &str) -> Result<(&str, &str, &str), Error> {
+    let pattern =
+        r"^\s*(?P<idGroup>\w+)\s*=\s*\((?P<leftGroup>\w+)\s*,\s*(?P<rightGroup>\w+)\)\s*$";
     let reg = regex::Regex::new(pattern).unwrap();
-    let captures = reg.captures(input).unwrap();
+    let captures = reg
+        .captures(input)
+        .ok_or_else(|| Error::parse(8, 1, "expected `ID = (LEFT, RIGHT)`"))?;
+
+    Ok((
+        captures.name("idGroup").unwrap().as_str(),
+        captures.name("leftGroup").unwrap().as_str(),
+        captures.name("rightGroup").unwrap().as_str(),
+    ))
+}
 
-    assert_eq!(captures.len(), 4); // 3 matches + first which is the full input
+fn parse_map(input: &str) -> Result<Map, Error> {
+    Map::new(input, "AAA")
+}
 
-    (
-        captures.index(1).to_owned(),
-        captures.index(2).to_owned(),
-        captures.index(3).to_owned(),
-    )
+// Ghosts start from every node ending with `A`.
+fn get_ghost_starts(map: &Map) -> Vec<&str> {
+    map.nodes
+        .iter()
+        .map(|node| node.id.as_str())
+        .filter(|id| id.ends_with('A'))
+        .collect()
 }
 
-fn parse_map(input: &str) -> Map {
-    Map::new(input, "AAA")
+// Steps from `start` to the first node ending with `Z`. A walk is in one of
+// `nodes * moves` states, so a longer one goes round in circles.
+fn count_steps(map: &Map, start: &str) -> Result<usize, Error> {
+    let limit = map.nodes.len() * map.moves.len();
+    let steps = map.iter_from(start).take(limit + 1).count();
+
+    if steps > limit {
+        return Err(Error::solve(
+            8,
+            format!("no node ending with `Z` can be reached from `{start}`"),
+        ));
+    }
+
+    Ok(steps)
 }
 
-fn parse_maps(input: &str) -> Vec<Map> {
-    let mut maps: Vec<Map> = Vec::new();
-    let (_, move_map) = input.trim().split_once('\n').unwrap();
+pub fn solve1(map: &Map) -> Result<usize, Error> {
+    if !map.nodes.iter().any(|node| node.id == map.start) {
+        return Err(Error::solve(
+            8,
+            format!("no `{}` node to start from", map.start),
+        ));
+    }
 
-    move_map
-        .lines()
-        .filter(|line| !line.trim().is_empty())
-        .enumerate()
-        .for_each(|(id, line)| {
-            let m = &line.trim()[0..3];
+    count_steps(map, &map.start)
+}
 
-            if m.ends_with("A") {
-                maps.push(Map::new(&input, m));
-            }
+pub fn solve2(map: &Map) -> Result<u64, Error> {
+    let mut total: Option<u64> = None;
+
+    for start in get_ghost_starts(map) {
+        let steps = count_steps(map, start)? as u64;
+        debug!("ghost from {start}: cycle of {steps} steps");
+
+        total = Some(match total {
+            None => steps,
+            Some(total) => (total / total.gcd(&steps))
+                .checked_mul(steps)
+                .ok_or_else(|| Error::solve(8, "number of steps overflows"))?,
         });
+    }
 
-    maps
+    total.ok_or_else(|| Error::solve(8, "no node ending with `A` to start from"))
 }
 
-pub fn solve1(input: &str) -> usize {
-    parse_map(input).iter().count()
-}
+pub struct Day8;
 
-pub fn solve2(input: &str) -> u64 {
-    let maps = parse_maps(input);
+impl Solution for Day8 {
+    type Input<'a> = Map;
+    type Answer1 = usize;
+    type Answer2 = u64;
 
-    maps.iter()
-        .map(|p| p.iter().count())
-        .reduce(|a, b| a.lcm(&b))
-        .unwrap() as u64
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        parse_map(input)
+    }
+
+    fn part1(map: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        solve1(map)
+    }
+
+    fn part2(map: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        solve2(map)
+    }
 }
 
 #[cfg(test)]
@@ -187,7 +259,7 @@ ZZZ = (ZZZ, ZZZ)";
 
     #[test]
     fn test_basic_iterator() {
-        let map = parse_map(&INPUT_6_STEPS);
+        let map = parse_map(INPUT_6_STEPS).unwrap();
         let steps = map.iter().count();
 
         assert_eq!(steps, 6);
@@ -195,7 +267,7 @@ ZZZ = (ZZZ, ZZZ)";
 
     #[test]
     fn test_iterator_when_aaa_is_not_first_line() {
-        let map = parse_map(&INPUT_2_STEPS_NOT_START);
+        let map = parse_map(INPUT_2_STEPS_NOT_START).unwrap();
 
         assert_eq!(map.nodes[3].id, "AAA");
         assert_eq!(map.nodes[map.nodes[3].left.unwrap()].id, "BBB");
@@ -210,39 +282,12 @@ ZZZ = (ZZZ, ZZZ)";
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    // 2 steps
-    const INPUT_2_STEPS: &str = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-
-    const INPUT_6_STEPS: &str = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-
-    const BONUS: &str = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
+    use crate::fixtures::example;
 
     #[test]
     fn test_line_parse_happy_path() {
         let input = "AAA = (BBB, CCC)";
-        let (id, left, right) = parse_map_entry(input);
+        let (id, left, right) = parse_map_entry(input).unwrap();
 
         assert_eq!(id, "AAA");
         assert_eq!(left, "BBB");
@@ -252,7 +297,7 @@ XXX = (XXX, XXX)";
     #[test]
     fn test_line_parse_lot_of_spaces_path() {
         let input = " AAA   =  (BBB   ,       CCC) ";
-        let (id, left, right) = parse_map_entry(input);
+        let (id, left, right) = parse_map_entry(input).unwrap();
 
         assert_eq!(id, "AAA");
         assert_eq!(left, "BBB");
@@ -261,7 +306,7 @@ XXX = (XXX, XXX)";
 
     #[test]
     fn test_parse_map_happy_path() {
-        let map = parse_map(INPUT_2_STEPS);
+        let map = parse_map(&example(8, 1)).unwrap();
 
         assert_eq!(map.moves, "RL");
         assert_eq!(map.nodes.len(), 7);
@@ -274,18 +319,106 @@ XXX = (XXX, XXX)";
     }
 
     #[test]
-    fn test_parse_maps() {
-        let maps = parse_maps(BONUS);
+    fn test_get_ghost_starts() {
+        let map = parse_map(&example(8, 2)).unwrap();
+        let starts = get_ghost_starts(&map);
 
-        assert_eq!(maps.len(), 2);
-        assert_eq!(maps[0].start, "11A");
-        assert_eq!(maps[1].start, "22A");
+        assert_eq!(starts, vec!["11A", "22A"]);
     }
 
     #[test]
     fn test_solve2() {
-        let maps = solve2(BONUS);
+        let maps = solve2(&parse_map(&example(8, 2)).unwrap()).unwrap();
 
         assert_eq!(maps, 6);
     }
+
+    #[test]
+    fn test_parse_map_unknown_node() {
+        let err = parse_map("RL\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)")
+            .err()
+            .unwrap();
+
+        assert_eq!(err, Error::parse(8, 8, "unknown node `BBB`").on_line(3));
+    }
+
+    #[test]
+    fn test_parse_map_invalid_entry() {
+        let err = parse_map("RL\n\nAAA = (ZZZ, ZZZ)\nZZZ = ZZZ")
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            err,
+            Error::parse(8, 1, "expected `ID = (LEFT, RIGHT)`").on_line(4)
+        );
+    }
+
+    #[test]
+    fn test_solve1_without_reachable_end() {
+        let map = parse_map("L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert_eq!(
+            solve1(&map),
+            Err(Error::solve(
+                8,
+                "no node ending with `Z` can be reached from `AAA`"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve2_without_reachable_end() {
+        let map = parse_map(
+            "LR\n\n11A = (11Z, 22A)\n11Z = (11B, 11B)\n11B = (11Z, 11Z)\n22A = (22A, 22A)",
+        )
+        .unwrap();
+
+        assert_eq!(
+            solve2(&map),
+            Err(Error::solve(
+                8,
+                "no node ending with `Z` can be reached from `22A`"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_solve1_without_start() {
+        let err = solve1(&parse_map(&example(8, 2)).unwrap()).unwrap_err();
+
+        assert_eq!(err, Error::solve(8, "no `AAA` node to start from"));
+    }
+}
+
+#[cfg(test)]
+mod tests_properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_map_entry_round_trip(
+            id in "[A-Z0-9]{1,5}",
+            left in "[A-Z0-9]{1,5}",
+            right in "[A-Z0-9]{1,5}",
+        ) {
+            let line = format!("{id} = ({left}, {right})");
+
+            prop_assert_eq!(
+                parse_map_entry(&line).unwrap(),
+                (id.as_str(), left.as_str(), right.as_str())
+            );
+        }
+
+        #[test]
+        fn test_map_entry_never_panics(line in "\\PC*") {
+            let _ = parse_map_entry(&line);
+        }
+
+        #[test]
+        fn test_map_never_panics(input in "[LRX]{0,4}\n\n([A-C]{1,2} = \\([A-C]{1,2}, [A-C]{1,2}\\)\n){0,4}") {
+            let _ = parse_map(&input);
+        }
+    }
 }
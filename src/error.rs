@@ -0,0 +1,148 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    // Malformed input. Lines and columns start at 1, columns count bytes.
+    Parse {
+        day: u32,
+        line: usize,
+        column: usize,
+        reason: String,
+    },
+    // Well-formed input the solver still cannot answer.
+    Solve {
+        day: u32,
+        reason: String,
+    },
+    // The part has no solver yet.
+    Unsolved {
+        day: u32,
+        part: u32,
+    },
+    // The input stopped being readable while streaming it.
+    Read {
+        reason: String,
+    },
+}
+
+impl Error {
+    // Parse error on the first line, see `on_line` to move it.
+    pub fn parse(day: u32, column: usize, reason: impl Into<String>) -> Error {
+        Error::Parse {
+            day,
+            line: 1,
+            column,
+            reason: reason.into(),
+        }
+    }
+
+    pub fn solve(day: u32, reason: impl Into<String>) -> Error {
+        Error::Solve {
+            day,
+            reason: reason.into(),
+        }
+    }
+
+    // Line parsers report line 1, the caller knows the real line.
+    pub fn on_line(self, line: usize) -> Error {
+        match self {
+            Error::Parse {
+                day,
+                column,
+                reason,
+                ..
+            } => Error::Parse {
+                day,
+                line,
+                column,
+                reason,
+            },
+            other => other,
+        }
+    }
+
+    // Moves the column of a parse error found in a slice of the line.
+    pub fn shifted(self, offset: usize) -> Error {
+        match self {
+            Error::Parse {
+                day,
+                line,
+                column,
+                reason,
+            } => Error::Parse {
+                day,
+                line,
+                column: column + offset,
+                reason,
+            },
+            other => other,
+        }
+    }
+
+    // Compiler-like diagnostic, e.g. `day4.txt:17:9: expected '|' ...`.
+    pub fn located(&self, source: &impl fmt::Display) -> String {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                reason,
+                ..
+            } => format!("{source}:{line}:{column}: {reason}"),
+            other => format!("{source}: {other}"),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                day,
+                line,
+                column,
+                reason,
+            } => write!(f, "day {day}, line {line}, column {column}: {reason}"),
+            Error::Solve { day, reason } => write!(f, "day {day}: {reason}"),
+            Error::Unsolved { day, part } => write!(f, "day {day} has no solver for part {part}"),
+            Error::Read { reason } => write!(f, "cannot read the input: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// 1-based column of `part`, which must be a slice of `line`.
+pub fn column_of(line: &str, part: &str) -> usize {
+    part.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_located_parse_error() {
+        let err =
+            Error::parse(4, 9, "expected '|' separating numbers and winning numbers").on_line(17);
+
+        assert_eq!(
+            err.located(&"day4.txt"),
+            "day4.txt:17:9: expected '|' separating numbers and winning numbers"
+        );
+    }
+
+    #[test]
+    fn test_shifted_keeps_line() {
+        let err = Error::parse(2, 3, "invalid").on_line(5).shifted(7);
+
+        assert_eq!(err, Error::parse(2, 10, "invalid").on_line(5));
+    }
+
+    #[test]
+    fn test_column_of() {
+        let line = "Card 1: 41 48";
+        let (_, numbers) = line.split_once(':').unwrap();
+
+        assert_eq!(column_of(line, numbers), 8);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::answers::KnownAnswers;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Directory of the example fixtures, in the crate directory.
+pub const FIXTURES_DIR: &str = "fixtures";
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub part: u32,
+    pub input: String,
+    // The last highlighted value of the part, which is its answer.
+    pub answer: Option<String>,
+}
+
+// `fixtures/dayN/partP.txt` under `root`.
+pub fn input_path(root: &Path, day: u32, part: u32) -> PathBuf {
+    root.join(FIXTURES_DIR)
+        .join(format!("day{day}"))
+        .join(format!("part{part}.txt"))
+}
+
+// `fixtures/dayN/answers.toml` under `root`, in the format of `answers.toml`.
+pub fn answers_path(root: &Path, day: u32) -> PathBuf {
+    root.join(FIXTURES_DIR)
+        .join(format!("day{day}"))
+        .join(crate::answers::ANSWERS_FILE)
+}
+
+// Text of an HTML fragment, without its tags.
+fn text(html: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+
+    tags.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+// Reads a saved puzzle page, one `<article>` per part. The example of a part is its first
+// `<pre><code>` block, or the one of the previous part when it has none.
+pub fn extract(page: &str) -> Vec<Example> {
+    let articles = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let blocks = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let highlights =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+
+    let mut examples: Vec<Example> = Vec::new();
+
+    for (part, article) in (1..).zip(articles.captures_iter(page)) {
+        let article = &article[1];
+        let input = match blocks.captures(article) {
+            Some(block) => text(&block[1]),
+            None => match examples.last() {
+                Some(previous) => previous.input.clone(),
+                None => continue,
+            },
+        };
+        let answer = highlights
+            .captures_iter(article)
+            .last()
+            .and_then(|captures| captures.get(1).or_else(|| captures.get(2)))
+            .map(|answer| text(answer.as_str()));
+
+        examples.push(Example {
+            part,
+            input,
+            answer,
+        });
+    }
+
+    examples
+}
+
+// Writes the inputs and the answers of the examples of `day` under `root`.
+// Returns the files written.
+pub fn write_fixtures(root: &Path, day: u32, examples: &[Example]) -> Result<Vec<PathBuf>, String> {
+    let mut written = Vec::new();
+    let answers_file = answers_path(root, day);
+    let mut answers = KnownAnswers::load(&answers_file)?;
+
+    for example in examples {
+        let path = input_path(root, day, example.part);
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("cannot create {}: {err}", dir.display()))?;
+        }
+
+        fs::write(&path, &example.input)
+            .map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+        written.push(path);
+
+        if let Some(answer) = &example.answer {
+            answers.set(day, example.part, answer);
+        }
+    }
+
+    if examples.iter().any(|example| example.answer.is_some()) {
+        answers.save(&answers_file)?;
+        written.push(answers_file);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed down page of day 6.
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 6: Wait For It ---</h2><p>For example:</p>
+<pre><code>Time:      7  15   30
+Distance:  9  40  200
+</code></pre>
+<p>So, in this example, the first race can be won in <code><em>4</em></code> ways.</p>
+<p>Multiply these values together, you get <code><em>288</em></code> (<code>4</code> * <code>8</code> * <code>9</code>).</p>
+</article>
+<p>Your puzzle answer was <code>1084752</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>So, the example from before now contains a race that lasts <code>71530</code> milliseconds &amp; <em>more</em>:</p>
+<p>In this example, you can win in <em><code>71503</code></em> ways.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract() {
+        assert_eq!(
+            extract(PAGE),
+            vec![
+                Example {
+                    part: 1,
+                    input: "Time:      7  15   30\nDistance:  9  40  200\n".to_owned(),
+                    answer: Some("288".to_owned()),
+                },
+                Example {
+                    part: 2,
+                    input: "Time:      7  15   30\nDistance:  9  40  200\n".to_owned(),
+                    answer: Some("71503".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_text_decodes_entities() {
+        assert_eq!(text("<em>a &lt;b&gt; &amp;lt;</em>"), "a <b> &lt;");
+    }
+
+    #[test]
+    fn test_write_fixtures() {
+        let root = std::env::temp_dir().join(format!("aoc_2023_fixtures_{}", std::process::id()));
+        let written = write_fixtures(&root, 6, &extract(PAGE)).unwrap();
+
+        assert_eq!(
+            written,
+            vec![
+                input_path(&root, 6, 1),
+                input_path(&root, 6, 2),
+                answers_path(&root, 6)
+            ]
+        );
+        assert_eq!(
+            KnownAnswers::load(&answers_path(&root, 6))
+                .unwrap()
+                .get(6, 2),
+            Some("71503".to_owned())
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+// Example inputs and answers of the puzzles, written under `fixtures/` by the `extract` command.
+use crate::answers::KnownAnswers;
+use crate::examples;
+use std::fs;
+use std::path::Path;
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+// Example input of a part, without its final line break.
+pub fn example(day: u32, part: u32) -> String {
+    let path = examples::input_path(root(), day, part);
+    let input = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("cannot read {}: {err}", path.display()));
+
+    input.trim_end_matches('\n').to_owned()
+}
+
+pub fn example_answer(day: u32, part: u32) -> String {
+    let answers = KnownAnswers::load(&examples::answers_path(root(), day)).unwrap();
+
+    answers
+        .get(day, part)
+        .unwrap_or_else(|| panic!("no example answer for day {day} part {part}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::registry::DAYS;
+
+    #[test]
+    fn test_every_day_solves_its_examples() {
+        for day in DAYS {
+            for part in [1, 2] {
+                let run = (day.solve)(&example(day.number, part), &[part]).unwrap();
+
+                match &run.parts[0].answer {
+                    Err(Error::Unsolved { .. }) => {}
+                    answer => assert_eq!(
+                        answer.as_deref(),
+                        Ok(example_answer(day.number, part).as_str()),
+                        "day {} part {part}",
+                        day.number
+                    ),
+                }
+            }
+        }
+    }
+}
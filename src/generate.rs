@@ -0,0 +1,493 @@
+use num::integer::Roots;
+use num::Integer;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+// A valid puzzle input with the answers it should give, when they are known.
+pub struct Generated {
+    pub input: String,
+    // Answers of part 1 and part 2. `None` when they cannot be worked out cheaply
+    // or do not fit the type returned by the solver.
+    pub answers: [Option<String>; 2],
+}
+
+// Size of the generated inputs when none is given.
+pub const DEFAULT_SIZE: usize = 1000;
+
+// Day 6 races beyond which the product of the ways to win, or the joined race,
+// would no longer fit in 64 bits.
+const MAX_RACES: usize = 4;
+
+// Generates an input for `day` from `seed`, `None` when the day has no generator.
+// `size` is roughly the number of lines, or of nodes for day 8, and at most
+// `MAX_RACES` races for day 6.
+pub fn generate(day: u32, seed: u64, size: usize) -> Option<Generated> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let size = size.max(1);
+
+    match day {
+        1 => Some(day1(&mut rng, size)),
+        2 => Some(day2(&mut rng, size)),
+        3 => Some(day3(&mut rng, size)),
+        4 => Some(day4(&mut rng, size)),
+        6 => Some(day6(&mut rng, size)),
+        8 => Some(day8(&mut rng, size)),
+        _ => None,
+    }
+}
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+// Letters found in no digit word, so the filler never spells one.
+const FILLER: &[u8] = b"abcdjklmpqy";
+
+// Calibration lines mixing digits, spelled digits and filler, with at least one digit.
+fn day1(rng: &mut StdRng, size: usize) -> Generated {
+    let mut lines = Vec::with_capacity(size);
+    let (mut digits_sum, mut words_sum) = (0, 0);
+
+    for _ in 0..size {
+        let count = rng.gen_range(1..=5);
+        let numeric = rng.gen_range(0..count);
+        // (value, spelled)
+        let tokens = (0..count)
+            .map(|idx| (rng.gen_range(1..=9), idx != numeric && rng.gen_bool(0.5)))
+            .collect::<Vec<(u64, bool)>>();
+
+        let mut line = String::new();
+        for (idx, (value, spelled)) in tokens.iter().enumerate() {
+            // Tokens are kept apart so that no word spans two of them.
+            let filler = rng.gen_range(usize::from(idx > 0)..=3);
+            line.extend((0..filler).map(|_| *FILLER.choose(rng).unwrap() as char));
+
+            match spelled {
+                true => line.push_str(WORDS[*value as usize - 1]),
+                false => line.push_str(&value.to_string()),
+            }
+        }
+        line.extend((0..rng.gen_range(0..=3)).map(|_| *FILLER.choose(rng).unwrap() as char));
+
+        let numbers = tokens
+            .iter()
+            .filter(|(_, spelled)| !spelled)
+            .map(|(value, _)| *value)
+            .collect::<Vec<u64>>();
+        digits_sum += numbers[0] * 10 + numbers[numbers.len() - 1];
+        words_sum += tokens[0].0 * 10 + tokens[tokens.len() - 1].0;
+        lines.push(line);
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        answers: [Some(digits_sum.to_string()), Some(words_sum.to_string())],
+    }
+}
+
+// Games of one to four sets, each showing some of the three colors.
+fn day2(rng: &mut StdRng, size: usize) -> Generated {
+    let mut lines = Vec::with_capacity(size);
+    let (mut valid_ids, mut power) = (0, 0);
+
+    for id in 1..=size as u64 {
+        // (red, green, blue) maximum of the game.
+        let mut max = [0; 3];
+        let sets = (0..rng.gen_range(1..=4))
+            .map(|_| {
+                let mut cubes = Vec::new();
+                for (idx, color) in ["red", "green", "blue"].into_iter().enumerate() {
+                    if rng.gen_bool(0.7) {
+                        let count = rng.gen_range(1..=20);
+                        max[idx] = max[idx].max(count);
+                        cubes.push(format!("{count} {color}"));
+                    }
+                }
+
+                if cubes.is_empty() {
+                    max[0] = max[0].max(1);
+                    cubes.push("1 red".to_owned());
+                }
+
+                cubes.shuffle(rng);
+                cubes.join(", ")
+            })
+            .collect::<Vec<String>>();
+
+        if max[0] <= 12 && max[1] <= 13 && max[2] <= 14 {
+            valid_ids += id;
+        }
+        power += max.iter().product::<u64>();
+        lines.push(format!("Game {id}: {}", sets.join("; ")));
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        answers: [Some(valid_ids.to_string()), Some(power.to_string())],
+    }
+}
+
+const SYMBOLS: &[u8] = b"*#+$/=@%&-";
+// Width and height of a day 3 block, without its separating column and row of dots.
+const BLOCK_WIDTH: usize = 8;
+const BLOCK_HEIGHT: usize = 3;
+
+// Fills a 3x8 block around a symbol at (1, 3), with numbers in fixed slots:
+//      123.....
+//      ...#456.
+//      12.7.123
+// The first three are adjacent to the symbol, the two others are not.
+// Returns the rows, the sum of the adjacent numbers and the gear ratio.
+fn day3_block(rng: &mut StdRng) -> (Vec<Vec<u8>>, u64, u64) {
+    let mut rows = vec![vec![b'.'; BLOCK_WIDTH]; BLOCK_HEIGHT];
+    let symbol = rng.gen_bool(0.8).then(|| *SYMBOLS.choose(rng).unwrap());
+    let mut adjacent = Vec::new();
+
+    // (row, first column, last column, maximum, right aligned, adjacent)
+    let slots = [
+        (0, 0, 2, 999, true, true),
+        (1, 4, 6, 999, false, true),
+        (2, 3, 3, 9, false, true),
+        (2, 0, 1, 99, false, false),
+        (2, 5, 7, 999, false, false),
+    ];
+
+    for (row, first, last, max, right_aligned, is_adjacent) in slots {
+        if !rng.gen_bool(0.6) {
+            continue;
+        }
+
+        let number = rng.gen_range(1..=max);
+        let digits = number.to_string();
+        let start = match right_aligned {
+            true => last + 1 - digits.len(),
+            false => first,
+        };
+        rows[row][start..start + digits.len()].copy_from_slice(digits.as_bytes());
+
+        if is_adjacent {
+            adjacent.push(number as u64);
+        }
+    }
+
+    let (sum, ratio) = match symbol {
+        Some(symbol) => {
+            rows[1][3] = symbol;
+            let ratio = match adjacent.as_slice() {
+                [a, b] if symbol == b'*' => a * b,
+                _ => 0,
+            };
+            (adjacent.iter().sum(), ratio)
+        }
+        None => (0, 0),
+    };
+
+    (rows, sum, ratio)
+}
+
+// A square of blocks, separated by dots so that each number only touches its own symbol.
+fn day3(rng: &mut StdRng, size: usize) -> Generated {
+    let side = (size as f64).sqrt().ceil() as usize;
+    let width = side * (BLOCK_WIDTH + 1);
+    let mut lines = Vec::new();
+    let (mut sum, mut ratios) = (0, 0);
+
+    for _ in 0..side {
+        let mut rows = vec![Vec::new(); BLOCK_HEIGHT];
+
+        for _ in 0..side {
+            let (block, block_sum, block_ratio) = day3_block(rng);
+            sum += block_sum;
+            ratios += block_ratio;
+
+            for (row, block_row) in rows.iter_mut().zip(block) {
+                row.extend(block_row);
+                row.push(b'.');
+            }
+        }
+
+        lines.extend(rows.into_iter().map(|row| String::from_utf8(row).unwrap()));
+        lines.push(".".repeat(width));
+    }
+
+    Generated {
+        input: lines.join("\n"),
+        answers: [Some(sum.to_string()), Some(ratios.to_string())],
+    }
+}
+
+// Cards never win copies past the last card.
+fn day4(rng: &mut StdRng, size: usize) -> Generated {
+    let mut lines = Vec::with_capacity(size);
+    let mut matches = Vec::with_capacity(size);
+    let pool = (1..100).collect::<Vec<u32>>();
+
+    for id in 1..=size {
+        let count = rng.gen_range(0..=5.min(size - id));
+        let mut numbers = pool.choose_multiple(rng, 13 - count).copied();
+        let winning = numbers.by_ref().take(5).collect::<Vec<u32>>();
+        let mut owned = winning[..count].to_vec();
+        owned.extend(numbers);
+        owned.shuffle(rng);
+
+        let format = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|number| format!("{number:>2}"))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        lines.push(format!(
+            "Card {id:>3}: {} | {}",
+            format(&winning),
+            format(&owned)
+        ));
+        matches.push(count);
+    }
+
+    let points = matches
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| 1 << (count - 1))
+        .sum::<u64>();
+
+    // `None` once the count overflows, as the solver then fails.
+    let mut copies = vec![Some(1_u64); size];
+    for (idx, count) in matches.iter().enumerate() {
+        for next in idx + 1..=idx + count {
+            copies[next] = copies[next]
+                .zip(copies[idx])
+                .and_then(|(a, b)| a.checked_add(b));
+        }
+    }
+    let total = copies
+        .iter()
+        .try_fold(0_u64, |total, count| total.checked_add((*count)?));
+
+    Generated {
+        input: lines.join("\n"),
+        answers: [
+            Some(points.to_string()),
+            total.map(|total| total.to_string()),
+        ],
+    }
+}
+
+// Number of hold times beating `distance`, counted from the roots of
+// `hold * (time - hold) = distance` rather than by searching like the solver.
+// A hold wins when `(2 * hold - time)^2 < time^2 - 4 * distance`, and every `k` of the
+// parity of `time` with `k^2` below that bound gives one hold per sign of `2 * hold - time`.
+fn day6_wins(time: u128, distance: u128) -> u128 {
+    let Some(bound) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
+
+    // Largest `k` with `k^2 < bound`, of the parity of `time`.
+    let root = bound.sqrt();
+    let mut k = match root * root < bound {
+        true => root,
+        false => match root.checked_sub(1) {
+            Some(k) => k,
+            None => return 0,
+        },
+    };
+    if k % 2 != time % 2 {
+        match k.checked_sub(1) {
+            Some(below) => k = below,
+            None => return 0,
+        }
+    }
+
+    k + 1
+}
+
+// Races whose record is set by holding for a random time.
+fn day6(rng: &mut StdRng, size: usize) -> Generated {
+    let races = (0..size.min(MAX_RACES))
+        .map(|_| {
+            let time = rng.gen_range(7..100_u64);
+            let hold = rng.gen_range(1..time / 2);
+            (time, hold * (time - hold), hold)
+        })
+        .collect::<Vec<(u64, u64, u64)>>();
+
+    let width = races
+        .iter()
+        .map(|(time, distance, _)| time.to_string().len().max(distance.to_string().len()))
+        .collect::<Vec<usize>>();
+    let line = |label: &str, value: fn(&(u64, u64, u64)) -> u64| {
+        races
+            .iter()
+            .zip(&width)
+            .fold(label.to_owned(), |line, (race, width)| {
+                format!("{line}  {:>width$}", value(race))
+            })
+    };
+
+    // Only the holds strictly between the one setting the record and its mirror beat it.
+    let product = races
+        .iter()
+        .map(|&(time, _, hold)| (time - 2 * hold - 1) as u128)
+        .try_fold(1_u128, |product, wins| product.checked_mul(wins));
+
+    let concatenated = |value: fn(&(u64, u64, u64)) -> u64| {
+        races
+            .iter()
+            .map(|race| value(race).to_string())
+            .collect::<String>()
+            .parse::<u64>()
+            .ok()
+    };
+    let single = concatenated(|race| race.0)
+        .zip(concatenated(|race| race.1))
+        .map(|(time, distance)| day6_wins(time as u128, distance as u128));
+
+    let known = |value: Option<u128>| {
+        value
+            .and_then(|value| u64::try_from(value).ok())
+            .map(|value| value.to_string())
+    };
+
+    Generated {
+        input: format!(
+            "{}\n{}",
+            line("Time:", |race| race.0),
+            line("Distance:", |race| race.1)
+        ),
+        answers: [known(product), known(single)],
+    }
+}
+
+// Ghosts walking cycles whose lengths are multiples of the moves, `AAA` to `ZZZ` being the first.
+// From the start and from the end node, both sides lead to the same nodes,
+// so each ghost is back on its end node every cycle.
+fn day8(rng: &mut StdRng, size: usize) -> Generated {
+    let moves = (0..rng.gen_range(1..=size.min(20)))
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect::<String>();
+    let ghosts = rng.gen_range(1..=6);
+    let max_cycles = (size / (moves.len() * ghosts)).max(1);
+
+    // (id, path to the end node) of every ghost.
+    let mut paths = Vec::new();
+    let mut next_id = 0;
+    for ghost in 0..ghosts {
+        let length = moves.len() * rng.gen_range(1..=max_cycles);
+        let (start, end) = match ghost {
+            0 => ("AAA".to_owned(), "ZZZ".to_owned()),
+            _ => (format!("G{ghost}A"), format!("G{ghost}Z")),
+        };
+
+        let mut path = vec![start];
+        for _ in 1..length {
+            next_id += 1;
+            path.push(format!("N{next_id}"));
+        }
+        path.push(end);
+        paths.push(path);
+    }
+
+    let ids = paths.iter().flatten().cloned().collect::<Vec<String>>();
+    let mut nodes = Vec::new();
+
+    for path in &paths {
+        let mut sides = Vec::new();
+
+        for (step, pair) in path.windows(2).enumerate() {
+            let decoy = ids.choose(rng).unwrap().clone();
+            let next = pair[1].clone();
+            let (left, right) = match moves.as_bytes()[step % moves.len()] {
+                b'L' => (next, decoy),
+                _ => (decoy, next),
+            };
+
+            nodes.push(format!("{} = ({left}, {right})", pair[0]));
+            sides.push((left, right));
+        }
+
+        let (left, right) = &sides[0];
+        nodes.push(format!("{} = ({left}, {right})", path[path.len() - 1]));
+    }
+    nodes.shuffle(rng);
+
+    let mut lengths = paths.iter().map(|path| path.len() as u64 - 1);
+    let steps = lengths.try_fold(1_u64, |steps, length| {
+        steps.checked_mul(length / steps.gcd(&length))
+    });
+
+    Generated {
+        input: format!("{moves}\n\n{}", nodes.join("\n")),
+        answers: [
+            Some((paths[0].len() - 1).to_string()),
+            steps.map(|steps| steps.to_string()),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::registry::DAYS;
+
+    #[test]
+    fn test_generated_answers_match_solvers() {
+        // A single seed at the default size, day 8 being slow to solve in debug builds.
+        let runs = [(3, 0..5), (50, 0..5), (DEFAULT_SIZE, 0..1)];
+
+        for (day, (size, seeds)) in DAYS
+            .iter()
+            .flat_map(|day| runs.clone().map(|run| (day, run)))
+        {
+            for seed in seeds {
+                let Some(generated) = generate(day.number, seed, size) else {
+                    continue;
+                };
+                let run = (day.solve)(&generated.input, &[1, 2]).unwrap();
+
+                for (part_run, expected) in run.parts.iter().zip(&generated.answers) {
+                    match (&part_run.answer, expected) {
+                        (Err(Error::Unsolved { .. }), _) | (_, None) => {}
+                        (answer, Some(expected)) => assert_eq!(
+                            answer.as_ref(),
+                            Ok(expected),
+                            "day {} part {} seed {seed} size {size}",
+                            day.number,
+                            part_run.part
+                        ),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_day6_wins() {
+        let wins = [
+            (7, 9),
+            (15, 40),
+            (30, 200),
+            (71530, 940200),
+            (4, 3),
+            (2, 1),
+            (0, 0),
+        ]
+        .map(|(time, distance)| day6_wins(time, distance));
+
+        assert_eq!(wins, [4, 8, 9, 71503, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_generate_is_deterministic() {
+        let first = generate(8, 42, 100).unwrap();
+        let second = generate(8, 42, 100).unwrap();
+
+        assert_eq!(first.input, second.input);
+        assert_ne!(first.input, generate(8, 43, 100).unwrap().input);
+    }
+
+    #[test]
+    fn test_generate_unknown_day() {
+        assert!(generate(25, 0, 10).is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::toml_file;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// Environment variable pointing to the directory holding the `dayN.txt` files.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+// Optional configuration file, looked up in the working directory.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+#[derive(Deserialize, Default)]
+pub struct Config {
+    pub input_dir: Option<PathBuf>,
+    // Accepted answers, `answers.toml` in the working directory by default.
+    pub answers: Option<PathBuf>,
+    // Site answers are submitted to, e.g. a local stub.
+    pub base_url: Option<String>,
+    // Submitted answers, `submissions.toml` in the working directory by default.
+    pub history: Option<PathBuf>,
+    // Answers cached by `run all`, `.aoc_cache.toml` in the working directory by default.
+    pub cache: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn answers_path(&self) -> PathBuf {
+        self.answers
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(crate::answers::ANSWERS_FILE))
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(crate::submit::DEFAULT_BASE_URL)
+    }
+
+    pub fn cache_path(&self) -> PathBuf {
+        self.cache
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(crate::cache::CACHE_FILE))
+    }
+
+    pub fn history_path(&self) -> PathBuf {
+        self.history
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(crate::submit::HISTORY_FILE))
+    }
+
+    pub fn load(path: &Path) -> Result<Config, String> {
+        toml_file::load(path)
+    }
+}
+
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    // Picks the input of a day, by order of priority:
+    // the explicit path (`-` being stdin), `AOC_INPUT_DIR`, `input_dir` from the configuration
+    // and finally the working directory.
+    pub fn resolve(day: u32, explicit: Option<&str>, config: &Config) -> InputSource {
+        if let Some(path) = explicit {
+            return match path {
+                "-" => InputSource::Stdin,
+                _ => InputSource::File(PathBuf::from(path)),
+            };
+        }
+
+        let dir = std::env::var_os(INPUT_DIR_VAR)
+            .map(PathBuf::from)
+            .or_else(|| config.input_dir.clone())
+            .unwrap_or_default();
+
+        InputSource::File(dir.join(format!("day{day}.txt")))
+    }
+
+    // `None` for stdin.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            InputSource::Stdin => None,
+            InputSource::File(path) => Some(path),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, String> {
+        let mut contents = String::new();
+
+        match self {
+            InputSource::Stdin => io::stdin().read_to_string(&mut contents).map(|_| contents),
+            InputSource::File(path) => fs::read_to_string(path),
+        }
+        .map_err(|err| format!("cannot read {self}: {err}"))
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_explicit_path() {
+        let config = Config::default();
+
+        match InputSource::resolve(3, Some("fixtures/engine.txt"), &config) {
+            InputSource::File(path) => assert_eq!(path, PathBuf::from("fixtures/engine.txt")),
+            InputSource::Stdin => panic!("Expected a file"),
+        }
+    }
+
+    #[test]
+    fn test_resolve_dash_is_stdin() {
+        let config = Config::default();
+
+        assert!(matches!(
+            InputSource::resolve(3, Some("-"), &config),
+            InputSource::Stdin
+        ));
+    }
+
+    #[test]
+    fn test_config_input_dir() {
+        let config: Config = toml::from_str("input_dir = \"inputs\"").unwrap();
+
+        assert_eq!(config.input_dir, Some(PathBuf::from("inputs")));
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let source = InputSource::File(PathBuf::from("does/not/exist/day1.txt"));
+        let err = source.read().unwrap_err();
+
+        assert!(err.starts_with("cannot read does/not/exist/day1.txt: "));
+    }
+}
@@ -0,0 +1,27 @@
+//! Advent of Code 2023 solvers, one module per day.
+
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod error;
+pub mod examples;
+#[cfg(test)]
+mod fixtures;
+pub mod generate;
+pub mod input;
+pub mod registry;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod stream;
+pub mod submit;
+mod toml_file;
+pub mod watch;
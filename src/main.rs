@@ -1,87 +1,791 @@
-use std::fs::File;
-use std::io::Read;
+use aoc_2023::answers::{KnownAnswers, Verdict};
+use aoc_2023::bench::Stats;
+use aoc_2023::cache::{self, AnswerCache};
+use aoc_2023::day1::{self, Mode};
+use aoc_2023::error::Error;
+use aoc_2023::input::{self, Config, InputSource};
+use aoc_2023::submit::{self, Client, History, Outcome};
+use aoc_2023::watch::Watcher;
+use aoc_2023::{examples, generate, registry, runner, scaffold};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use log::LevelFilter;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
 
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-//mod day5;
-mod day6;
-mod day8;
-//mod day7;
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
 
-fn solve_day1() {
-    let mut file = File::open("day1.txt").unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+    /// Logs what the solvers do to stderr, `-v` for debug and `-vv` for trace events.
+    /// `RUST_LOG` takes precedence.
+    #[arg(long, short, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solver of a day, or of every registered day.
+    Run {
+        /// Day number (1 to 25) or `all`.
+        day: Selection,
+
+        /// Only run this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Also report the parse time and the time of each part.
+        #[arg(long, short)]
+        time: bool,
+
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Solve again even when the answer of this input is cached.
+        /// Only `run all` uses the cache, a single day is always solved.
+        #[arg(long)]
+        force: bool,
+
+        /// Prints the first and last token of each line and the value read from them.
+        /// Day 1 only.
+        #[arg(long, conflicts_with_all = ["time", "format", "force"])]
+        explain: bool,
+    },
+    /// Runs the solver of a day repeatedly and reports the min, median and max time of each stage.
+    Bench {
+        /// Day number (1 to 25) or `all`.
+        day: Selection,
+
+        /// Number of runs.
+        #[arg(long, short = 'n', default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Re-runs the solver of a day, with timings, whenever its input changes.
+    /// Source changes need a rebuild.
+    Watch {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// Only run this part of the puzzle.
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+
+        #[command(flatten)]
+        input: InputArgs,
+
+        /// Scratch file, e.g. the example of the puzzle, also run on every change.
+        #[arg(long, short)]
+        example: Option<PathBuf>,
+
+        /// Milliseconds between two checks of the files.
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Prints a random input of a day to stdout, and its known answers to stderr.
+    Generate {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// Roughly the number of lines, or of nodes for day 8.
+        #[arg(long, default_value_t = generate::DEFAULT_SIZE)]
+        size: usize,
+    },
+    /// Records the accepted answer of a puzzle part in `answers.toml`.
+    Record {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+
+        answer: String,
+    },
+    /// Solves a puzzle part and posts the answer to the site, unless it is already known to be wrong.
+    /// Needs the session cookie of the site in `AOC_SESSION`.
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+
+        #[command(flatten)]
+        input: InputArgs,
+    },
+    /// Runs every registered day and compares its answers with `answers.toml`.
+    Verify,
+    /// Writes the example inputs and answers of a saved puzzle page to `fixtures/dayN/`.
+    /// Must be run from the crate directory.
+    Extract {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+
+        /// The puzzle page, saved once both parts are unlocked.
+        page: PathBuf,
+    },
+    /// Generates `src/dayN.rs`, registers it and creates an empty input file.
+    /// Must be run from the crate directory.
+    NewDay {
+        #[arg(value_parser = clap::value_parser!(u32).range(1..=25))]
+        day: u32,
+    },
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Input file, or `-` to read stdin. Defaults to `dayN.txt` in `AOC_INPUT_DIR`,
+    /// then in the `input_dir` of `aoc.toml`, then in the working directory.
+    #[arg(long, short)]
+    input: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// One line per answer.
+    Text,
+    /// A JSON array with one object per answer.
+    Json,
+}
 
-    let value = day1::get_calibration_value(contents.to_ascii_lowercase().as_str());
+#[derive(Clone, Copy)]
+enum Selection {
+    All,
+    Day(u32),
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if input.eq_ignore_ascii_case("all") {
+            return Ok(Selection::All);
+        }
+
+        match input.parse::<u32>() {
+            Ok(day) if (1..=25).contains(&day) => Ok(Selection::Day(day)),
+            _ => Err(format!("unknown day `{input}`, expected 1 to 25 or `all`")),
+        }
+    }
+}
+
+// Registered days matching `selection`. An explicit input only makes sense for a single day.
+fn select_days(
+    selection: Selection,
+    input: &InputArgs,
+) -> Result<Vec<&'static registry::Day>, String> {
+    match selection {
+        Selection::All if input.input.is_some() => {
+            Err("--input can only be used when running a single day".to_owned())
+        }
+        Selection::All => Ok(registry::DAYS.iter().collect()),
+        Selection::Day(number) => registry::find(number)
+            .map(|day| vec![day])
+            .ok_or(format!("day {number} has no solver")),
+    }
+}
 
-    println!("Day1: {value}");
+#[derive(Serialize)]
+struct AnswerRecord {
+    day: u32,
+    part: u32,
+    answer: String,
+    duration_ns: u64,
+    // `None` when read from stdin.
+    input_path: Option<String>,
+    // Taken from the cache instead of solving, `duration_ns` is then 0.
+    cached: bool,
 }
 
-fn solve_day2() {
-    let mut file = File::open("day2.txt").unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+struct DayRun {
+    // `Solution::VERSION` of the day.
+    version: u32,
+    input_hash: u64,
+    parse_duration: Duration,
+    answers: Vec<AnswerRecord>,
+    // Parts that have no solver yet.
+    unsolved: Vec<u32>,
+}
+
+impl DayRun {
+    fn store(&self, cache: &mut AnswerCache) {
+        for record in self.answers.iter().filter(|record| !record.cached) {
+            cache.insert(
+                record.day,
+                record.part,
+                self.version,
+                self.input_hash,
+                &record.answer,
+            );
+        }
+    }
+}
+
+// Prints how day 1 reads each line, e.g.
+//      line 4: "xtwone3four" first="two"@1 last="four"@7 -> 24
+fn explain_day1(
+    selection: Selection,
+    part: Option<u32>,
+    input: &InputArgs,
+    config: &Config,
+) -> Result<(), String> {
+    if !matches!(selection, Selection::Day(1)) {
+        return Err("--explain is only available for day 1".to_owned());
+    }
+
+    let contents = InputSource::resolve(1, input.input.as_deref(), config).read()?;
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    for part in parts {
+        let mut sum = 0_u64;
+        let vocabulary = Mode::for_part(part).vocabulary();
 
-    let value_valid_sum =
-        day2::get_valid_games_id_sums(contents.to_ascii_lowercase().as_str(), 14, 12, 13);
-    let value_power = day2::get_games_power(contents.to_ascii_lowercase().as_str());
+        for (number, explanation) in day1::explain_calibration(&contents, vocabulary) {
+            println!("line {number}: {explanation}");
+            sum = sum
+                .checked_add(explanation.value as u64)
+                .ok_or("sum of the calibration values overflows")?;
+        }
+        println!("Day 1 part {part}: {sum}");
+    }
 
-    println!("Day2 valid sum: {value_valid_sum}");
-    println!("Day2 power: {value_power}");
+    Ok(())
 }
 
-fn solve_day3() {
-    let mut file = File::open("day3.txt").unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+// Runs the requested part, or both parts when `part` is `None`.
+// The parts found in `cache` are not solved, the input is not even parsed when all are.
+fn run_day(
+    day: &registry::Day,
+    part: Option<u32>,
+    input: &InputArgs,
+    config: &Config,
+    cache: Option<&AnswerCache>,
+) -> Result<DayRun, String> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
 
-    let answer = day3::get_final_value(contents.as_str());
-    let answer2 = day3::get_gears_value(contents.as_str());
-    println!("Day3: {answer}");
-    println!("Day3 Gears: {answer2}");
+    let source = InputSource::resolve(day.number, input.input.as_deref(), config);
+    let contents = source.read()?;
+    let input_path = source.path().map(|path| path.display().to_string());
+    let mut day_run = DayRun {
+        version: day.version,
+        input_hash: cache::input_hash(&contents),
+        parse_duration: Duration::ZERO,
+        answers: Vec::new(),
+        unsolved: Vec::new(),
+    };
+
+    let (cached, missing): (Vec<u32>, Vec<u32>) = parts.into_iter().partition(|part| {
+        cache.is_some_and(|cache| {
+            cache
+                .get(day.number, *part, day.version, day_run.input_hash)
+                .is_some()
+        })
+    });
+
+    for part in cached {
+        let answer = cache
+            .and_then(|cache| cache.get(day.number, part, day.version, day_run.input_hash))
+            .unwrap_or_default();
+
+        day_run.answers.push(AnswerRecord {
+            day: day.number,
+            part,
+            answer: answer.to_owned(),
+            duration_ns: 0,
+            input_path: input_path.clone(),
+            cached: true,
+        });
+    }
+
+    if !missing.is_empty() {
+        let run = (day.solve)(&contents, &missing).map_err(|err| err.located(&source))?;
+        day_run.parse_duration = run.parse_duration;
+
+        for part_run in run.parts {
+            match part_run.answer {
+                Ok(answer) => day_run.answers.push(AnswerRecord {
+                    day: day.number,
+                    part: part_run.part,
+                    answer,
+                    duration_ns: part_run.duration.as_nanos() as u64,
+                    input_path: input_path.clone(),
+                    cached: false,
+                }),
+                Err(Error::Unsolved { .. }) => day_run.unsolved.push(part_run.part),
+                Err(err) => return Err(err.located(&source)),
+            }
+        }
+    }
+
+    day_run.answers.sort_by_key(|record| record.part);
+    Ok(day_run)
 }
 
-fn solve_day4() {
-    let mut file = File::open("day4.txt").unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+// Single days are solved without the cache, so every answer has its time.
+fn print_day_run(day: u32, day_run: &DayRun, time: bool) {
+    if time {
+        println!("Day {day} parse: {:?}", day_run.parse_duration);
+    }
 
-    let answer = day4::solve_scratchcards(contents.as_str());
-    let answer2 = day4::solve_bonus_total_scratchcards(contents.as_str());
-    println!("Day4: {answer}");
-    println!("Day4 cards: {answer2}");
+    for record in &day_run.answers {
+        if time {
+            let duration = Duration::from_nanos(record.duration_ns);
+            println!(
+                "Day {day} part {}: {} ({duration:?})",
+                record.part, record.answer
+            );
+        } else {
+            println!("Day {day} part {}: {}", record.part, record.answer);
+        }
+    }
 }
 
-fn solve_day6() {
-    let mut file = File::open("day6.txt").unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+fn print_table(rows: &[[String; 4]]) {
+    let mut widths = [0; 4];
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
 
-    let answer = day6::get_wins_product(contents.as_str());
-    let answer2 = day6::get_possible_wins_single(contents.as_str());
-    println!("Day6: {answer}");
-    println!("Day6 bonus: {answer2}");
+    for [day, part, answer, time] in rows {
+        let line = format!(
+            "{day:>w0$}  {part:>w1$}  {answer:<w2$}  {time:>w3$}",
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        println!("{}", line.trim_end());
+    }
 }
 
-fn solve_day8() {
-    let mut file = File::open("day8.txt").unwrap();
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).unwrap();
+// Runs every day on its own thread and prints a summary, or the JSON records.
+// Fails when any day failed, after reporting all of them.
+fn run_all(
+    part: Option<u32>,
+    format: Format,
+    config: &Config,
+    cache: &mut AnswerCache,
+    force: bool,
+) -> Result<(), String> {
+    let no_input = InputArgs { input: None };
+    let lookup = (!force).then_some(&*cache);
+    let start = Instant::now();
+    let results = runner::run_parallel(registry::DAYS, |day| {
+        run_day(day, part, &no_input, config, lookup)
+    });
+    let wall_time = start.elapsed();
 
-    let answer = day8::solve1(contents.as_str());
-    let answer2 = day8::solve2(contents.as_str());
-    println!("Day8: {answer}");
-    println!("Day8 bonus: {answer2}");
+    let mut rows = vec![["Day", "Part", "Answer", "Time"].map(str::to_owned)];
+    let mut records = Vec::new();
+    let mut total = Duration::ZERO;
+    let mut failures = 0;
+
+    for (number, result) in results {
+        match result {
+            Ok(day_run) => {
+                total += day_run.parse_duration;
+                day_run.store(cache);
+
+                for record in &day_run.answers {
+                    let duration = Duration::from_nanos(record.duration_ns);
+                    total += duration;
+                    rows.push([
+                        number.to_string(),
+                        record.part.to_string(),
+                        record.answer.clone(),
+                        match record.cached {
+                            true => "cached".to_owned(),
+                            false => format!("{duration:?}"),
+                        },
+                    ]);
+                }
+
+                records.extend(day_run.answers);
+            }
+            Err(message) => {
+                failures += 1;
+                eprintln!("error: day {number}: {message}");
+                rows.push([
+                    number.to_string(),
+                    "-".to_owned(),
+                    "error".to_owned(),
+                    "-".to_owned(),
+                ]);
+            }
+        }
+    }
+
+    match format {
+        Format::Text => {
+            rows.push([
+                "Total".to_owned(),
+                String::new(),
+                format!("wall {wall_time:?}"),
+                format!("{total:?}"),
+            ]);
+            print_table(&rows);
+        }
+        Format::Json => {
+            let json = serde_json::to_string_pretty(&records).map_err(|err| err.to_string())?;
+            println!("{json}");
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!("{failures} days failed")),
+    }
 }
 
-fn main() {
-    //solve_day1();
-    //solve_day2();
-    //solve_day3();
-    //solve_day4();
-    //solve_day6();
-    solve_day8();
+fn print_stats(day: u32, stage: &str, samples: &[Duration]) {
+    if let Some(stats) = Stats::new(samples) {
+        println!(
+            "Day {day} {stage}: min {:?}, median {:?}, max {:?}",
+            stats.min, stats.median, stats.max
+        );
+    }
+}
+
+fn bench_day(
+    day: &registry::Day,
+    iterations: u32,
+    input: &InputArgs,
+    config: &Config,
+) -> Result<(), String> {
+    let source = InputSource::resolve(day.number, input.input.as_deref(), config);
+    let contents = source.read()?;
+    let mut parse_samples = Vec::new();
+    let mut part_samples = [Vec::new(), Vec::new()];
+
+    for _ in 0..iterations {
+        let run = (day.solve)(&contents, &[1, 2]).map_err(|err| err.located(&source))?;
+        parse_samples.push(run.parse_duration);
+
+        for part_run in run.parts {
+            match part_run.answer {
+                Ok(_) => part_samples[part_run.part as usize - 1].push(part_run.duration),
+                Err(Error::Unsolved { .. }) => {}
+                Err(err) => return Err(err.located(&source)),
+            }
+        }
+    }
+
+    print_stats(day.number, "parse", &parse_samples);
+    print_stats(day.number, "part 1", &part_samples[0]);
+    print_stats(day.number, "part 2", &part_samples[1]);
+
+    Ok(())
+}
+
+// Never returns unless the day or the input is invalid, errors of a run are only reported.
+fn watch(
+    day: u32,
+    part: Option<u32>,
+    input: &InputArgs,
+    example: Option<PathBuf>,
+    interval: Duration,
+    config: &Config,
+) -> Result<(), String> {
+    let day = select_days(Selection::Day(day), input)?[0];
+    let mut paths = match InputSource::resolve(day.number, input.input.as_deref(), config) {
+        InputSource::File(path) => vec![path],
+        InputSource::Stdin => return Err("cannot watch stdin".to_owned()),
+    };
+    paths.extend(example);
+
+    let mut watcher = Watcher::new(paths);
+
+    loop {
+        for path in watcher.poll() {
+            let input = InputArgs {
+                input: Some(path.display().to_string()),
+            };
+
+            println!("== {} ==", path.display());
+            match run_day(day, part, &input, config, None) {
+                Ok(day_run) => print_day_run(day.number, &day_run, true),
+                Err(message) => println!("error: {message}"),
+            }
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+// Fails when any answer differs from the recorded one or cannot be computed.
+fn verify(config: &Config) -> Result<(), String> {
+    let answers = KnownAnswers::load(&config.answers_path())?;
+    let mut failures = 0;
+
+    for day in registry::DAYS.iter() {
+        let source = InputSource::resolve(day.number, None, config);
+        let results = source.read().and_then(|contents| {
+            (day.solve)(&contents, &[1, 2]).map_err(|err| err.located(&source))
+        });
+
+        let results = match results {
+            Ok(results) => results,
+            Err(message) => {
+                println!("Day {}: error: {message}", day.number);
+                failures += 1;
+                continue;
+            }
+        };
+
+        for part_run in results.parts {
+            let part = part_run.part;
+
+            match part_run.answer {
+                Ok(answer) => {
+                    let verdict = answers.check(day.number, part, &answer);
+
+                    if matches!(verdict, Verdict::Fail { .. }) {
+                        failures += 1;
+                    }
+
+                    println!("Day {} part {part}: {verdict}", day.number);
+                }
+                Err(Error::Unsolved { .. }) => {}
+                Err(err) => {
+                    println!(
+                        "Day {} part {part}: error: {}",
+                        day.number,
+                        err.located(&source)
+                    );
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!("{failures} answers failed verification")),
+    }
+}
+
+fn submit_answer(day: u32, part: u32, input: &InputArgs, config: &Config) -> Result<(), String> {
+    let registered = select_days(Selection::Day(day), input)?[0];
+    let answer = match run_day(registered, Some(part), input, config, None)?
+        .answers
+        .pop()
+    {
+        Some(record) => record.answer,
+        None => return Err(format!("day {day} has no solver for part {part}")),
+    };
+
+    let history_path = config.history_path();
+    let mut history = History::load(&history_path)?;
+    history
+        .check(day, part, &answer)
+        .map_err(|reason| format!("not submitting day {day} part {part}: {reason}"))?;
+
+    let session = std::env::var(submit::SESSION_VAR)
+        .map_err(|_| format!("{} must hold the session cookie", submit::SESSION_VAR))?;
+    let outcome = Client::new(config.base_url(), &session).submit(day, part, &answer)?;
+
+    history.record(day, part, &answer, &outcome);
+    history.save(&history_path)?;
+
+    if outcome != Outcome::Right {
+        return Err(format!("day {day} part {part}: {answer}: {outcome}"));
+    }
+
+    let answers_path = config.answers_path();
+    let mut answers = KnownAnswers::load(&answers_path)?;
+    answers.set(day, part, &answer);
+    answers.save(&answers_path)?;
+
+    println!("Day {day} part {part}: {answer}: {outcome}");
+    Ok(())
+}
+
+fn execute(command: Command, config: &Config) -> Result<(), String> {
+    match command {
+        Command::Run {
+            day,
+            part,
+            input,
+            explain: true,
+            ..
+        } => explain_day1(day, part, &input, config),
+        Command::Run {
+            day: Selection::All,
+            part,
+            input,
+            format,
+            force,
+            ..
+        } => {
+            // Only checks that no input was given.
+            select_days(Selection::All, &input)?;
+
+            let path = config.cache_path();
+            let mut cache = AnswerCache::load(&path)?;
+            let result = run_all(part, format, config, &mut cache, force);
+            cache.save(&path)?;
+            result
+        }
+        Command::Run {
+            day: selection,
+            part,
+            input,
+            time,
+            format,
+            ..
+        } => {
+            let mut records = Vec::new();
+
+            for day in select_days(selection, &input)? {
+                let day_run = run_day(day, part, &input, config, None)?;
+
+                // Running everything skips the unsolved parts, asking for one is an error.
+                if let (Selection::Day(number), Some(part), [_]) =
+                    (selection, part, day_run.unsolved.as_slice())
+                {
+                    return Err(format!("day {number} has no solver for part {part}"));
+                }
+
+                match format {
+                    Format::Text => print_day_run(day.number, &day_run, time),
+                    Format::Json => records.extend(day_run.answers),
+                }
+            }
+
+            if let Format::Json = format {
+                let json = serde_json::to_string_pretty(&records).map_err(|err| err.to_string())?;
+                println!("{json}");
+            }
+
+            Ok(())
+        }
+        Command::Bench {
+            day: selection,
+            iterations,
+            input,
+        } => select_days(selection, &input)?
+            .into_iter()
+            .try_for_each(|day| bench_day(day, iterations, &input, config)),
+        Command::Watch {
+            day,
+            part,
+            input,
+            example,
+            interval,
+        } => watch(
+            day,
+            part,
+            &input,
+            example,
+            Duration::from_millis(interval),
+            config,
+        ),
+        Command::Generate { day, seed, size } => {
+            let generated = generate::generate(day, seed, size)
+                .ok_or(format!("day {day} has no input generator"))?;
+
+            println!("{}", generated.input);
+            for (part, answer) in (1..).zip(&generated.answers) {
+                if let Some(answer) = answer {
+                    eprintln!("Day {day} part {part}: {answer}");
+                }
+            }
+
+            Ok(())
+        }
+        Command::Record { day, part, answer } => {
+            let path = config.answers_path();
+            let mut answers = KnownAnswers::load(&path)?;
+            answers.set(day, part, answer.trim());
+            answers.save(&path)
+        }
+        Command::Submit { day, part, input } => submit_answer(day, part, &input, config),
+        Command::Verify => verify(config),
+        Command::Extract { day, page } => {
+            let root = Path::new(".");
+
+            if !root.join("Cargo.toml").exists() {
+                return Err("extract must be run from the crate directory".to_owned());
+            }
+
+            let contents = std::fs::read_to_string(&page)
+                .map_err(|err| format!("cannot read {}: {err}", page.display()))?;
+            let found = examples::extract(&contents);
+
+            if found.is_empty() {
+                return Err(format!("no puzzle description in {}", page.display()));
+            }
+
+            for path in examples::write_fixtures(root, day, &found)? {
+                println!("Wrote {}", path.display());
+            }
+
+            Ok(())
+        }
+        Command::NewDay { day } => {
+            let root = Path::new(".");
+
+            if !root.join("Cargo.toml").exists() {
+                return Err("new-day must be run from the crate directory".to_owned());
+            }
+
+            let input = match InputSource::resolve(day, None, config) {
+                InputSource::File(path) => path,
+                InputSource::Stdin => unreachable!("only an explicit input reads stdin"),
+            };
+
+            for path in scaffold::new_day(root, day, &input)? {
+                println!("Wrote {}", path.display());
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let level = match cli.verbose {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .format_timestamp(None)
+        .parse_default_env()
+        .init();
+    let result = Config::load(Path::new(input::CONFIG_FILE))
+        .and_then(|config| execute(cli.command, &config));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
 }
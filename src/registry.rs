@@ -0,0 +1,37 @@
+use crate::error::Error;
+use crate::solution::{self, Run, Solution};
+use crate::{day1, day2, day3, day4, day5, day6, day7, day8};
+
+pub struct Day {
+    pub number: u32,
+    // `Solution::VERSION` of the solver.
+    pub version: u32,
+    // Parses the input once and runs the given parts.
+    pub solve: fn(&str, &[u32]) -> Result<Run, Error>,
+}
+
+impl Day {
+    const fn new<S: Solution>(number: u32) -> Day {
+        Day {
+            number,
+            version: S::VERSION,
+            solve: solution::solve::<S>,
+        }
+    }
+}
+
+// Every day with a solver, in order.
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+    Day::new::<day8::Day8>(8),
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,64 @@
+use crate::registry::Day;
+use std::any::Any;
+use std::thread;
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown reason".to_owned());
+
+    format!("panicked: {message}")
+}
+
+// Runs `job` for every day on its own thread, keeping the order of `days`.
+// A day that fails or panics does not stop the others.
+pub fn run_parallel<T, F>(days: &[Day], job: F) -> Vec<(u32, Result<T, String>)>
+where
+    T: Send,
+    F: Fn(&Day) -> Result<T, String> + Sync,
+{
+    thread::scope(|scope| {
+        let handles = days
+            .iter()
+            .map(|day| (day.number, scope.spawn(|| job(day))))
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|(number, handle)| {
+                (
+                    number,
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| Err(panic_message(panic))),
+                )
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::DAYS;
+
+    #[test]
+    fn test_run_parallel_isolates_failures() {
+        let results = run_parallel(&DAYS[..3], |day| match day.number {
+            2 => panic!("day 2 exploded"),
+            3 => Err("no input".to_owned()),
+            number => Ok(number * 10),
+        });
+
+        assert_eq!(
+            results,
+            vec![
+                (1, Ok(10)),
+                (2, Err("panicked: day 2 exploded".to_owned())),
+                (3, Err("no input".to_owned())),
+            ]
+        );
+    }
+}
@@ -0,0 +1,311 @@
+use crate::examples;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Skeleton of a new day, `__N__` being the day number.
+const DAY_TEMPLATE: &str = r#"use crate::error::Error;
+use crate::solution::Solution;
+
+fn get_lines(input: &str) -> Result<Vec<String>, Error> {
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+pub struct Day__N__;
+
+impl Solution for Day__N__ {
+    type Input<'a> = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+        get_lines(input)
+    }
+
+    fn part1(_input: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+        Err(Error::Unsolved { day: __N__, part: 1 })
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+        Err(Error::Unsolved { day: __N__, part: 2 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::example;
+
+    #[test]
+    fn test_part1_example() {
+        let input = Day__N__::parse(&example(__N__, 1)).unwrap();
+
+        assert_eq!(
+            Day__N__::part1(&input),
+            Err(Error::Unsolved { day: __N__, part: 1 })
+        );
+    }
+
+    #[test]
+    fn test_part2_example() {
+        let input = Day__N__::parse(&example(__N__, 2)).unwrap();
+
+        assert_eq!(
+            Day__N__::part2(&input),
+            Err(Error::Unsolved { day: __N__, part: 2 })
+        );
+    }
+}
+"#;
+
+pub fn day_module(day: u32) -> String {
+    DAY_TEMPLATE.replace("__N__", &day.to_string())
+}
+
+// Index of the line where a new `day` line goes, among the lines matching `pattern`
+// (whose first group is the day number). `None` when there is no such line.
+fn insertion_index(lines: &[&str], pattern: &Regex, day: u32) -> Option<usize> {
+    let numbered = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let number = pattern
+                .captures(line)?
+                .get(1)?
+                .as_str()
+                .parse::<u32>()
+                .ok()?;
+            Some((idx, number))
+        })
+        .collect::<Vec<(usize, u32)>>();
+
+    numbered
+        .iter()
+        .find(|(_, number)| *number > day)
+        .map(|(idx, _)| *idx)
+        .or_else(|| numbered.last().map(|(idx, _)| idx + 1))
+}
+
+fn join_lines(lines: &[&str]) -> String {
+    lines.join("\n") + "\n"
+}
+
+// Declares `pub mod dayN;` in `lib.rs`, uncommenting it when it is already there.
+pub fn add_module(lib_rs: &str, day: u32) -> Result<String, String> {
+    let declaration = format!("pub mod day{day};");
+    let mut lines = lib_rs.lines().collect::<Vec<&str>>();
+
+    if lines.iter().any(|line| line.trim() == declaration) {
+        return Err(format!("day{day} is already declared"));
+    }
+
+    if let Some(line) = lines
+        .iter_mut()
+        .find(|line| line.trim() == format!("//{declaration}"))
+    {
+        *line = &declaration;
+        return Ok(join_lines(&lines));
+    }
+
+    let pattern = Regex::new(r"^(?://)?pub mod day(\d+);$").unwrap();
+    let idx = insertion_index(&lines, &pattern, day).unwrap_or(lines.len());
+    lines.insert(idx, &declaration);
+
+    Ok(join_lines(&lines))
+}
+
+// Imports the module of the day and adds it to `DAYS` in `registry.rs`.
+pub fn add_registry_entry(registry_rs: &str, day: u32) -> Result<String, String> {
+    let entry = format!("    Day::new::<day{day}::Day{day}>({day}),");
+    let mut lines = registry_rs.lines().collect::<Vec<&str>>();
+
+    if lines.contains(&entry.as_str()) {
+        return Err(format!("day{day} is already registered"));
+    }
+
+    let entry_pattern = Regex::new(r"^\s*Day::new::<day(\d+)::Day\d+>\(\d+\),$").unwrap();
+    let idx = insertion_index(&lines, &entry_pattern, day)
+        .ok_or("cannot find the `DAYS` entries of the registry")?;
+    lines.insert(idx, &entry);
+
+    // e.g. `use crate::{day1, day2, day3};`
+    let import_pattern = Regex::new(r"^use crate::\{(day\d+(?:, day\d+)*)\};$").unwrap();
+    let import_idx = lines
+        .iter()
+        .position(|line| import_pattern.is_match(line))
+        .ok_or("cannot find the import of the day modules in the registry")?;
+
+    let mut modules = import_pattern.captures(lines[import_idx]).unwrap()[1]
+        .split(", ")
+        .filter_map(|module| module.strip_prefix("day")?.parse::<u32>().ok())
+        .collect::<Vec<u32>>();
+    modules.push(day);
+    modules.sort();
+    modules.dedup();
+
+    let import = format!(
+        "use crate::{{{}}};",
+        modules
+            .iter()
+            .map(|number| format!("day{number}"))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    lines[import_idx] = &import;
+
+    Ok(join_lines(&lines))
+}
+
+// Creates `src/dayN.rs`, declares and registers it, and creates an empty `input` file
+// and empty example fixtures, until `extract` fills them.
+// `root` is the crate directory. Returns the files created or changed.
+pub fn new_day(root: &Path, day: u32, input: &Path) -> Result<Vec<PathBuf>, String> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|err| format!("cannot read {}: {err}", path.display()))
+    };
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|err| format!("cannot write {}: {err}", path.display()))
+    };
+
+    let module = root.join("src").join(format!("day{day}.rs"));
+    let lib = root.join("src").join("lib.rs");
+    let registry = root.join("src").join("registry.rs");
+
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+
+    // Edit everything in memory first so a failure leaves the tree untouched.
+    let lib_rs = add_module(&read(&lib)?, day)?;
+    let registry_rs = add_registry_entry(&read(&registry)?, day)?;
+
+    write(&module, &day_module(day))?;
+    write(&lib, &lib_rs)?;
+    write(&registry, &registry_rs)?;
+    let mut changed = vec![module, lib, registry];
+
+    let fixtures = [1, 2].map(|part| examples::input_path(root, day, part));
+    for path in fixtures.iter().map(PathBuf::as_path).chain([input]) {
+        if path.exists() {
+            continue;
+        }
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("cannot create {}: {err}", dir.display()))?;
+        }
+
+        write(path, "")?;
+        changed.push(path.to_owned());
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB_RS: &str = "pub mod answers;
+pub mod day1;
+pub mod day4;
+//pub mod day5;
+pub mod day6;
+pub mod error;
+";
+
+    const REGISTRY_RS: &str = "use crate::{day1, day4, day6};
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day6::Day6>(6),
+];
+";
+
+    #[test]
+    fn test_add_module_in_order() {
+        let lib_rs = add_module(LIB_RS, 2).unwrap();
+
+        assert!(lib_rs.contains("pub mod day1;\npub mod day2;\npub mod day4;\n"));
+    }
+
+    #[test]
+    fn test_add_module_uncomments() {
+        let lib_rs = add_module(LIB_RS, 5).unwrap();
+
+        assert!(lib_rs.contains("pub mod day4;\npub mod day5;\npub mod day6;\n"));
+        assert!(!lib_rs.contains("//pub mod day5;"));
+    }
+
+    #[test]
+    fn test_add_module_last() {
+        let lib_rs = add_module(LIB_RS, 9).unwrap();
+
+        assert!(lib_rs.contains("pub mod day6;\npub mod day9;\npub mod error;\n"));
+    }
+
+    #[test]
+    fn test_add_module_twice() {
+        assert!(add_module(LIB_RS, 4).is_err());
+    }
+
+    #[test]
+    fn test_add_registry_entry() {
+        let registry_rs = add_registry_entry(REGISTRY_RS, 5).unwrap();
+
+        assert_eq!(
+            registry_rs,
+            "use crate::{day1, day4, day5, day6};
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+];
+"
+        );
+    }
+
+    #[test]
+    fn test_add_registry_entry_twice() {
+        assert!(add_registry_entry(REGISTRY_RS, 6).is_err());
+    }
+
+    #[test]
+    fn test_day_module() {
+        let module = day_module(12);
+
+        assert!(module.contains("pub struct Day12;"));
+        assert!(module.contains("Err(Error::Unsolved { day: 12, part: 2 })"));
+        assert!(!module.contains("__N__"));
+        assert!(module.contains("Day12::parse(&example(12, 2))"));
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc_2023_scaffold_{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src").join("lib.rs"), LIB_RS).unwrap();
+        fs::write(root.join("src").join("registry.rs"), REGISTRY_RS).unwrap();
+
+        let input = root.join("inputs").join("day9.txt");
+        let changed = new_day(&root, 9, &input).unwrap();
+
+        assert_eq!(
+            changed[3..],
+            [
+                examples::input_path(&root, 9, 1),
+                examples::input_path(&root, 9, 2),
+                input
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(examples::input_path(&root, 9, 2)).unwrap(),
+            ""
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
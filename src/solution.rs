@@ -0,0 +1,114 @@
+use crate::error::Error;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+pub struct PartRun {
+    pub part: u32,
+    // `Error::Unsolved` for the parts without a solver.
+    pub answer: Result<String, Error>,
+    pub duration: Duration,
+}
+
+pub struct Run {
+    pub parse_duration: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+// Common shape of every day: the input is parsed once, then shared by both parts.
+pub trait Solution {
+    // May borrow from the input it is parsed from.
+    type Input<'a>;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    // Bumped whenever a fix changes the answer of some input, which discards the cached answers.
+    const VERSION: u32 = 1;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error>;
+
+    fn part1(input: &Self::Input<'_>) -> Result<Self::Answer1, Error>;
+    fn part2(input: &Self::Input<'_>) -> Result<Self::Answer2, Error>;
+}
+
+// Parses `input` once and runs the requested parts, keeping their order.
+// Every stage is timed; formatting the answer is part of the timing.
+pub fn solve<S: Solution>(input: &str, parts: &[u32]) -> Result<Run, Error> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_duration = start.elapsed();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = match part {
+                1 => S::part1(&parsed).map(|answer| answer.to_string()),
+                _ => S::part2(&parsed).map(|answer| answer.to_string()),
+            };
+
+            PartRun {
+                part,
+                answer,
+                duration: start.elapsed(),
+            }
+        })
+        .collect();
+
+    Ok(Run {
+        parse_duration,
+        parts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Input<'a> = Vec<String>;
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input<'_>, Error> {
+            match input.lines().position(str::is_empty) {
+                Some(idx) => Err(Error::parse(0, 1, "empty line").on_line(idx + 1)),
+                None => Ok(input.lines().map(str::to_owned).collect()),
+            }
+        }
+
+        fn part1(lines: &Self::Input<'_>) -> Result<Self::Answer1, Error> {
+            Ok(lines.len())
+        }
+
+        fn part2(_lines: &Self::Input<'_>) -> Result<Self::Answer2, Error> {
+            Err(Error::Unsolved { day: 0, part: 2 })
+        }
+    }
+
+    #[test]
+    fn test_solve_keeps_parts_order() {
+        let run = solve::<Lines>("a\nb\nc", &[2, 1]).unwrap();
+        let answers = run
+            .parts
+            .into_iter()
+            .map(|part_run| (part_run.part, part_run.answer))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            answers,
+            vec![
+                (2, Err(Error::Unsolved { day: 0, part: 2 })),
+                (1, Ok("3".to_owned()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_parse_error() {
+        let err = solve::<Lines>("a\n\nc", &[1, 2]).err().unwrap();
+
+        assert_eq!(err, Error::parse(0, 1, "empty line").on_line(2));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::error::Error;
+use std::io::BufRead;
+
+// Folds the lines of `reader` one at a time, without keeping more than the current one.
+// `f` also gets the 1-based line number. Line endings are stripped as by `str::lines`.
+pub fn fold_lines<R, T, F>(mut reader: R, init: T, mut f: F) -> Result<T, Error>
+where
+    R: BufRead,
+    F: FnMut(T, &str, usize) -> Result<T, Error>,
+{
+    let mut acc = init;
+    let mut line = String::new();
+    let mut line_number = 0;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).map_err(|err| Error::Read {
+            reason: err.to_string(),
+        })?;
+
+        if read == 0 {
+            return Ok(acc);
+        }
+
+        line_number += 1;
+        let content = line.strip_suffix('\n').unwrap_or(&line);
+        let content = content.strip_suffix('\r').unwrap_or(content);
+        acc = f(acc, content, line_number)?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_fold_lines_like_str_lines() {
+        let input = "a\r\nbb\n\nccc";
+        let lines = fold_lines(Cursor::new(input), Vec::new(), |mut lines, line, number| {
+            lines.push((number, line.to_owned()));
+            Ok(lines)
+        })
+        .unwrap();
+
+        assert_eq!(
+            lines,
+            input
+                .lines()
+                .enumerate()
+                .map(|(idx, line)| (idx + 1, line.to_owned()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_fold_lines_invalid_utf8() {
+        let result = fold_lines(Cursor::new(b"ok\n\xff\n"), 0, |count, _, _| Ok(count + 1));
+
+        assert!(matches!(result, Err(Error::Read { .. })));
+    }
+}
@@ -0,0 +1,291 @@
+use crate::toml_file;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+// Environment variable holding the value of the `session` cookie of the site.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+// Default file of the submitted answers, in the working directory.
+pub const HISTORY_FILE: &str = "submissions.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // `wait` as given by the site, e.g. `4m 32s`.
+    RateLimited { wait: Option<String> },
+    AlreadySolved,
+    // The page did not look like any known answer.
+    Unknown,
+}
+
+impl Outcome {
+    // Reads the page returned after posting an answer.
+    pub fn parse(page: &str) -> Outcome {
+        if page.contains("That's the right answer") {
+            Outcome::Right
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if page.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if page.contains("You gave an answer too recently") {
+            let wait = Regex::new(r"You have (.+?) left to wait").unwrap();
+            Outcome::RateLimited {
+                wait: wait.captures(page).map(|captures| captures[1].to_owned()),
+            }
+        } else if page.contains("You don't seem to be solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    // Name kept in the history, `None` for the outcomes not worth keeping.
+    fn key(&self) -> Option<&'static str> {
+        match self {
+            Outcome::Right => Some("right"),
+            Outcome::Wrong => Some("wrong"),
+            Outcome::TooHigh => Some("too high"),
+            Outcome::TooLow => Some("too low"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::RateLimited { wait: Some(wait) } => write!(f, "rate limited, {wait} left"),
+            Outcome::RateLimited { wait: None } => write!(f, "rate limited"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::Unknown => write!(f, "unknown response"),
+            other => write!(f, "{}", other.key().unwrap()),
+        }
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    // `base_url` is the site root, e.g. `https://adventofcode.com`.
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+        }
+    }
+
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Outcome, String> {
+        let url = format!("{}/2023/day/{day}/answer", self.base_url);
+        let response = ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "aoc_2023 submit command")
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| format!("cannot post to {url}: {err}"))?;
+
+        response
+            .into_string()
+            .map(|page| Outcome::parse(&page))
+            .map_err(|err| format!("cannot read the response of {url}: {err}"))
+    }
+}
+
+// Answers already submitted, keyed by day and part, e.g.
+//      [day3.part1]
+//      4360 = "too low"
+//      4361 = "right"
+#[derive(Default)]
+pub struct History {
+    days: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History, String> {
+        toml_file::load(path).map(|days| History { days })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        toml_file::save(path, &self.days)
+    }
+
+    fn submissions(&self, day: u32, part: u32) -> impl Iterator<Item = (&String, &String)> {
+        self.days
+            .get(&format!("day{day}"))
+            .and_then(|parts| parts.get(&format!("part{part}")))
+            .into_iter()
+            .flatten()
+    }
+
+    // Why `answer` should not be submitted, if it is already known to be wrong
+    // or lies beyond an answer known to be too high or too low.
+    pub fn check(&self, day: u32, part: u32, answer: &str) -> Result<(), String> {
+        let value = answer.parse::<i128>().ok();
+
+        for (submitted, outcome) in self.submissions(day, part) {
+            let bound = submitted.parse::<i128>().ok().zip(value);
+
+            match outcome.as_str() {
+                "right" => return Err(format!("already solved with {submitted}")),
+                _ if submitted == answer => {
+                    return Err(format!("{answer} was already submitted: {outcome}"))
+                }
+                "too high" if bound.is_some_and(|(high, value)| value > high) => {
+                    return Err(format!("{answer} is above {submitted}, which is too high"))
+                }
+                "too low" if bound.is_some_and(|(low, value)| value < low) => {
+                    return Err(format!("{answer} is below {submitted}, which is too low"))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    // Only keeps the outcomes saying something about the answer.
+    pub fn record(&mut self, day: u32, part: u32, answer: &str, outcome: &Outcome) {
+        if let Some(key) = outcome.key() {
+            self.days
+                .entry(format!("day{day}"))
+                .or_default()
+                .entry(format!("part{part}"))
+                .or_default()
+                .insert(answer.to_owned(), key.to_owned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Serves `page` to a single request and returns the request line, headers and body.
+    fn stub_server(page: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse::<usize>().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                page.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_submit_to_stub_server() {
+        let (base_url, server) = stub_server(
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+        let outcome = Client::new(&base_url, "cookie").submit(3, 1, "4360");
+        let request = server.join().unwrap();
+
+        assert_eq!(outcome, Ok(Outcome::TooLow));
+        assert!(request.starts_with("POST /2023/day/3/answer HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=cookie\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=4360"));
+    }
+
+    #[test]
+    fn test_parse_outcomes() {
+        let outcomes = [
+            "That's the right answer! You are one gold star closer to restoring snow operations.",
+            "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+            "That's not the right answer; your answer is too high.",
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 38s left to wait.",
+            "You don't seem to be solving the right level.  Did you already complete it?",
+            "<html>Please log in</html>",
+        ]
+        .map(Outcome::parse);
+
+        assert_eq!(
+            outcomes,
+            [
+                Outcome::Right,
+                Outcome::Wrong,
+                Outcome::TooHigh,
+                Outcome::RateLimited {
+                    wait: Some("38s".to_owned())
+                },
+                Outcome::AlreadySolved,
+                Outcome::Unknown,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_refuses_known_answers() {
+        let mut history = History::default();
+        history.record(3, 1, "4360", &Outcome::TooLow);
+        history.record(3, 1, "5000", &Outcome::TooHigh);
+        history.record(3, 1, "4400", &Outcome::Wrong);
+        history.record(3, 1, "4401", &Outcome::RateLimited { wait: None });
+
+        assert!(history.check(3, 1, "4360").is_err());
+        assert!(history.check(3, 1, "4400").is_err());
+        assert!(history.check(3, 1, "12").is_err());
+        assert!(history.check(3, 1, "6000").is_err());
+        assert!(history.check(3, 1, "4401").is_ok());
+        assert!(history.check(3, 2, "4360").is_ok());
+
+        history.record(3, 1, "4361", &Outcome::Right);
+        assert_eq!(
+            history.check(3, 1, "4401"),
+            Err("already solved with 4361".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let mut history = History::default();
+        history.record(8, 2, "13", &Outcome::TooHigh);
+        let contents = toml::to_string(&history.days).unwrap();
+        let history = History {
+            days: toml::from_str(&contents).unwrap(),
+        };
+
+        assert_eq!(
+            history.check(8, 2, "13"),
+            Err("13 was already submitted: too high".to_owned())
+        );
+    }
+}
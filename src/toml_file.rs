@@ -0,0 +1,57 @@
+// Tables kept in TOML files, read and written whole: the configuration,
+// the accepted answers, the submissions and the answer cache.
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// A missing file is the default table, an invalid one is an error.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => toml::from_str(&contents)
+            .map_err(|err| format!("invalid {}: {}", path.display(), err.message())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(err) => Err(format!("cannot read {}: {err}", path.display())),
+    }
+}
+
+pub fn save<T: Serialize>(path: &Path, table: &T) -> Result<(), String> {
+    let contents = toml::to_string(table).map_err(|err| err.to_string())?;
+
+    fs::write(path, contents).map_err(|err| format!("cannot write {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    type Table = BTreeMap<String, BTreeMap<String, String>>;
+
+    #[test]
+    fn test_load_missing_is_default() {
+        let path = std::env::temp_dir().join("aoc_2023_toml_file_missing.toml");
+
+        assert_eq!(load::<Table>(&path), Ok(Table::new()));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("aoc_2023_toml_file_{}.toml", std::process::id()));
+        let mut table = Table::new();
+        table
+            .entry("day3".to_owned())
+            .or_default()
+            .insert("part1".to_owned(), "4361".to_owned());
+
+        save(&path, &table).unwrap();
+        assert_eq!(load::<Table>(&path), Ok(table));
+
+        fs::write(&path, "[day3\npart1 = 1").unwrap();
+        assert!(load::<Table>(&path).unwrap_err().starts_with("invalid "));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Polls the modification time of files. A missing file has no time,
+// so creating or deleting it also counts as a change.
+pub struct Watcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    polled: bool,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        Watcher {
+            files: paths.into_iter().map(|path| (path, None)).collect(),
+            polled: false,
+        }
+    }
+
+    // Files modified since the previous poll, in the order they were given.
+    // The first poll reports every file.
+    pub fn poll(&mut self) -> Vec<&Path> {
+        let first = !self.polled;
+        self.polled = true;
+
+        self.files
+            .iter_mut()
+            .filter_map(|(path, last)| {
+                let current = modified(path);
+                let changed = first || current != *last;
+                *last = current;
+                changed.then_some(path.as_path())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    #[test]
+    fn test_poll_reports_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc_2023_watch_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("day8.txt");
+        let example = dir.join("example.txt");
+        fs::write(&input, "LR\n").unwrap();
+        let _ = fs::remove_file(&example);
+
+        let mut watcher = Watcher::new(vec![input.clone(), example.clone()]);
+
+        assert_eq!(watcher.poll(), vec![input.as_path(), example.as_path()]);
+        assert!(watcher.poll().is_empty());
+
+        let later = SystemTime::now() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(watcher.poll(), vec![input.as_path()]);
+
+        fs::write(&example, "RL\n").unwrap();
+        assert_eq!(watcher.poll(), vec![example.as_path()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}